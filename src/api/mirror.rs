@@ -0,0 +1,277 @@
+// caching Initializr mirror for a team behind a slow uplink: `spring-tui serve` answers the
+// capabilities document, /dependencies, starter.zip, pom.xml and build.gradle from a disk cache and
+// goes to the configured baseUrl on a miss. The links in the capabilities are rewritten to the
+// mirror so downloads go through it as well. Stale entries are served when upstream is down, and
+// with `cache_only` upstream is never contacted, which makes a filled cache a local stand-in.
+use crate::api::transport::{HttpResponse, Transport, TransportError};
+use crate::generator::lock::sha256_hex;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Paths of the Initializr API the mirror serves
+pub const ROUTES: &[&str] = &["/", "/dependencies", "/starter.zip", "/pom.xml", "/build.gradle"];
+
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+/// Time a client gets to send the request head before the connection is dropped
+const REQUEST_HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Mirror {
+    /// Initializr instance behind the mirror, with a trailing slash
+    pub upstream: String,
+    pub cache_dir: PathBuf,
+    /// age after which an entry is fetched again
+    pub max_age: Duration,
+    /// answer from the cache only, never contact upstream
+    pub cache_only: bool,
+    /// how upstream is reached, usually the installed transport
+    pub transport: Arc<dyn Transport>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: String,
+    pub body: Vec<u8>,
+    /// hit, miss, stale or pass (not cacheable)
+    pub cache: &'static str,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Response { status, content_type: "text/plain".to_string(), body: message.as_bytes().to_vec(), cache: "pass" }
+    }
+}
+
+struct Cached {
+    content_type: String,
+    body: Vec<u8>,
+    fresh: bool,
+}
+
+impl Mirror {
+    fn cached(&self, key: &str) -> Option<Cached> {
+        let body_path = self.cache_dir.join(format!("{}.body", key));
+        let body = fs::read(&body_path).ok()?;
+        let content_type = fs::read_to_string(self.cache_dir.join(format!("{}.type", key))).ok()?;
+        let age = fs::metadata(&body_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .unwrap_or_default();
+        Some(Cached { content_type, body, fresh: age <= self.max_age })
+    }
+
+    fn store(&self, key: &str, content_type: &str, body: &[u8]) -> std::io::Result<()> {
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(self.cache_dir.join(format!("{}.type", key)), content_type)?;
+        fs::write(self.cache_dir.join(format!("{}.body", key)), body)
+    }
+
+    async fn fetch(&self, target: &str, accept: &str) -> Result<HttpResponse, TransportError> {
+        let url = format!("{}{}", self.upstream, target.trim_start_matches('/'));
+        self.transport.get(&url, Some(accept)).await
+    }
+
+    /// Answer `GET target`; `host` is how the client reached the mirror, used in rewritten links
+    pub async fn handle(&self, target: &str, accept: Option<&str>, host: &str) -> Response {
+        let path = target.split('?').next().unwrap_or("/");
+        if !ROUTES.contains(&path) {
+            return Response::error(404, "Not an Initializr API path");
+        }
+        let accept = accept.unwrap_or("*/*");
+        let key = sha256_hex(format!("{}\n{}", target, accept).as_bytes());
+        let cached = self.cached(&key);
+
+        let mut response = match cached {
+            Some(entry) if entry.fresh || self.cache_only => {
+                Response { status: 200, content_type: entry.content_type, body: entry.body, cache: "hit" }
+            }
+            None if self.cache_only => return Response::error(504, "Not in the mirror cache"),
+            cached => match (self.fetch(target, accept).await, cached) {
+                (Ok(HttpResponse { status: 200, content_type, body }), _) => {
+                    if let Err(e) = self.store(&key, &content_type, &body) {
+                        eprintln!("! could not cache {}: {}", target, e);
+                    }
+                    Response { status: 200, content_type, body, cache: "miss" }
+                }
+                // keep serving what we have while upstream is down
+                (Ok(HttpResponse { status: 500.., .. }) | Err(_), Some(entry)) => {
+                    Response { status: 200, content_type: entry.content_type, body: entry.body, cache: "stale" }
+                }
+                (Ok(HttpResponse { status, content_type, body }), _) => {
+                    Response { status, content_type, body, cache: "pass" }
+                }
+                (Err(e), None) => return Response::error(502, &format!("Upstream {} unreachable: {}", self.upstream, e)),
+            },
+        };
+
+        if path == "/" && response.content_type.contains("json") {
+            let body = String::from_utf8_lossy(&response.body).replace(&self.upstream, &format!("http://{}/", host));
+            response.body = body.into_bytes();
+        }
+        response
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        502 => "Bad Gateway",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
+/// Request head up to the blank line, None when the client hung up or sent too much
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<Option<Vec<u8>>> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 4096];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 || head.len() > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    Ok(Some(head))
+}
+
+async fn connection(mut stream: TcpStream, mirror: &Mirror) -> std::io::Result<()> {
+    let Ok(head) = tokio::time::timeout(REQUEST_HEAD_TIMEOUT, read_head(&mut stream)).await else {
+        return Ok(());
+    };
+    let Some(head) = head? else {
+        return Ok(());
+    };
+    let head = String::from_utf8_lossy(&head);
+    let request_line: Vec<&str> = head.lines().next().unwrap_or_default().split_whitespace().collect();
+    let header = |name: &str| {
+        head.lines()
+            .skip(1)
+            .filter_map(|l| l.split_once(':'))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.trim().to_string())
+    };
+    let host = header("host").unwrap_or_else(|| stream.local_addr().map(|a| a.to_string()).unwrap_or_default());
+
+    let response = match request_line.as_slice() {
+        ["GET", target, ..] => mirror.handle(target, header("accept").as_deref(), &host).await,
+        [_, _, ..] => Response::error(405, "Only GET is supported"),
+        _ => Response::error(400, "Malformed request"),
+    };
+    println!(
+        "{} {} {}",
+        request_line.get(1).unwrap_or(&"-"),
+        response.status,
+        response.cache
+    );
+
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nX-Spring-Tui-Cache: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        response.content_type,
+        response.body.len(),
+        response.cache
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&response.body).await?;
+    stream.shutdown().await
+}
+
+/// Serve `mirror` on `listener` until the process ends
+pub async fn serve(listener: TcpListener, mirror: Arc<Mirror>) -> std::io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let mirror = mirror.clone();
+        tokio::spawn(async move {
+            if let Err(e) = connection(stream, &mirror).await {
+                eprintln!("! mirror connection failed: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::transport::ResponseFuture;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Upstream answering the first request and failing every one after it
+    #[derive(Default)]
+    struct Upstream {
+        requests: AtomicUsize,
+    }
+
+    impl Transport for Upstream {
+        fn get<'a>(&'a self, url: &'a str, _accept: Option<&'a str>) -> ResponseFuture<'a> {
+            let status = if self.requests.fetch_add(1, Ordering::SeqCst) == 0 { 200 } else { 500 };
+            Box::pin(async move {
+                Ok(HttpResponse { status, content_type: "application/zip".to_string(), body: url.as_bytes().to_vec() })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_the_cache_with_links_pointing_at_the_mirror() {
+        let mirror = Mirror {
+            upstream: "https://start.spring.io/".to_string(),
+            cache_dir: std::env::temp_dir().join(format!("spring-tui-mirror-{}", std::process::id())),
+            max_age: Duration::from_secs(60),
+            cache_only: true,
+            transport: Arc::new(Upstream::default()),
+        };
+        let accept = "application/vnd.initializr.v2.3+json";
+        let capabilities = r#"{"_links":{"maven-project":{"href":"https://start.spring.io/starter.zip?type=maven-project{&dependencies}"}}}"#;
+        mirror.store(&sha256_hex(format!("/\n{}", accept).as_bytes()), "application/json", capabilities.as_bytes()).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, Arc::new(mirror)));
+
+        let response = reqwest::Client::new().get(format!("http://{}/", address)).header("accept", accept).send().await.unwrap();
+        assert_eq!(response.headers()["x-spring-tui-cache"], "hit");
+        let body = response.text().await.unwrap();
+        assert!(body.contains(&format!("http://{}/starter.zip?type=maven-project", address)));
+
+        let missing = reqwest::get(format!("http://{}/starter.zip?type=gradle-project", address)).await.unwrap();
+        assert_eq!(missing.status().as_u16(), 504);
+        assert_eq!(reqwest::get(format!("http://{}/actuator", address)).await.unwrap().status().as_u16(), 404);
+    }
+
+    #[tokio::test]
+    async fn serves_stale_entries_while_upstream_fails() {
+        let cache_dir = std::env::temp_dir().join(format!("spring-tui-mirror-stale-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let upstream = Arc::new(Upstream::default());
+        let mirror = Mirror {
+            upstream: "https://start.spring.io/".to_string(),
+            cache_dir: cache_dir.clone(),
+            max_age: Duration::ZERO,
+            cache_only: false,
+            transport: upstream.clone(),
+        };
+        let target = "/starter.zip?type=maven-project";
+
+        let miss = mirror.handle(target, None, "localhost").await;
+        assert_eq!((miss.status, miss.cache), (200, "miss"));
+        assert!(mirror.cached(&sha256_hex(format!("{}\n*/*", target).as_bytes())).is_some());
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let stale = mirror.handle(target, None, "localhost").await;
+        assert_eq!((stale.status, stale.cache), (200, "stale"));
+        assert_eq!(stale.body, miss.body);
+        assert_eq!(upstream.requests.load(Ordering::SeqCst), 2);
+
+        let _ = fs::remove_dir_all(&cache_dir);
+    }
+}
@@ -1,50 +1,49 @@
-mod util;
-use crate::types;
+pub mod mirror;
+pub mod snapshot;
+pub mod transport;
+pub mod util;
 use crate::types::api::{InitializrCapabilities, InitializrDependencies};
-use crate::api::util::{get_base_url, get_headers};
+use crate::api::snapshot::Backend;
+use crate::api::util::{get_base_url, ACCEPT_JSON};
+use serde::de::DeserializeOwned;
 use std::error::Error;
 
 pub async fn get_dependencies() -> Result<InitializrDependencies, Box<dyn Error>> {
-    let client = reqwest::Client::new();
-
-    let response = client
-        .get(get_base_url() + "dependencies")
-        .headers(get_headers())
-        .send()
-        .await?;
+    get_json(get_base_url() + "dependencies", snapshot::DEPENDENCIES).await
+}
 
-    if response.status().is_success() {
-        let deps = response.json::<InitializrDependencies>().await?;
-        Ok(deps)
-    } else {
-        let error_text = response.text().await.unwrap_or_default();
-        let error_response: Result<types::generic::ErrorResponse, _> = serde_json::from_str(&error_text);
-        if let Ok(err) = error_response {
-            Err(format!("Download failed: {}",err.message).into())
-        } else {  
-            Err(format!("Download failed: {}", error_text).into())
-        }
-    }
+/// Dependency coordinates as resolved for a specific Spring Boot version. Offline only the
+/// snapshot for that version will do, the generic one has the coordinates of the default version.
+pub async fn get_dependencies_for(boot_version: &str) -> Result<InitializrDependencies, Box<dyn Error>> {
+    get_json(
+        format!("{}dependencies?bootVersion={}", get_base_url(), boot_version),
+        &snapshot::dependencies_for(boot_version),
+    )
+    .await
 }
 
 pub async fn get_capabilities() -> Result<InitializrCapabilities, Box<dyn Error>> {
-    let client = reqwest::Client::new();
-    let response = client
-        .get(get_base_url())
-        .headers(get_headers())
-        .send()
-        .await?;
+    get_json(get_base_url(), snapshot::CAPABILITIES).await
+}
+
+/// GET `url`, keeping the response in the snapshot as `name`. Offline, or with the auto
+/// backend when Initializr is unreachable, the snapshot answers instead.
+async fn get_json<T: DeserializeOwned>(url: String, name: &str) -> Result<T, Box<dyn Error>> {
+    let body = match snapshot::backend() {
+        Backend::Offline => snapshot::load(name)?,
+        backend => match get_text(url).await {
+            Ok(body) => {
+                snapshot::save(name, &body);
+                body
+            }
+            Err(e) if backend == Backend::Auto && snapshot::is_unreachable(e.as_ref()) => snapshot::load(name)?,
+            Err(e) => return Err(e),
+        },
+    };
+    Ok(serde_json::from_str(&body)?)
+}
 
-    if response.status().is_success() {
-        let capabilities = response.json::<InitializrCapabilities>().await?;
-        Ok(capabilities)
-    } else {
-        let error_text = response.text().await.unwrap_or_default();
-        let error_response: Result<types::generic::ErrorResponse, _> = serde_json::from_str(&error_text);
-        if let Ok(err) = error_response {
-            Err(format!("Download failed: {}",err.message).into())
-        } else {  
-            Err(format!("Download failed: {}", error_text).into())
-        }
-    }
+async fn get_text(url: String) -> Result<String, Box<dyn Error>> {
+    let body = transport::get(&url, Some(ACCEPT_JSON)).await?.into_success()?;
+    Ok(String::from_utf8(body)?)
 }
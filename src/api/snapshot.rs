@@ -0,0 +1,89 @@
+// snapshot of the Initializr metadata for machines without access to it: every capabilities and
+// /dependencies response is saved to <cache dir>/snapshot (or `snapshotDir`), and read back when
+// the backend is `offline`, or `auto` and Initializr cannot be reached. `spring-tui snapshot`
+// fills it for every Boot version so the directory can be copied to an air-gapped machine.
+use crate::config::util::get_application_cache_dir;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+pub const CAPABILITIES: &str = "capabilities";
+pub const DEPENDENCIES: &str = "dependencies";
+
+/// Where projects come from
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Backend {
+    #[default]
+    Initializr,
+    /// never touch the network, generate with generator::offline from the snapshot
+    Offline,
+    /// Initializr, switching to the offline backend when it cannot be reached
+    Auto,
+}
+
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "initializr" => Ok(Backend::Initializr),
+            "offline" => Ok(Backend::Offline),
+            "auto" => Ok(Backend::Auto),
+            _ => Err(format!("backend must be 'initializr', 'offline' or 'auto', got '{}'", s)),
+        }
+    }
+}
+
+static BACKEND: OnceLock<Backend> = OnceLock::new();
+static SNAPSHOT_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Backend used by every request; only the first call has an effect
+pub fn set_backend(backend: Backend) {
+    let _ = BACKEND.set(backend);
+}
+
+pub fn backend() -> Backend {
+    BACKEND.get().copied().unwrap_or_default()
+}
+
+/// Read and write the snapshot in `dir` instead of the cache; only the first call has an effect
+pub fn set_snapshot_dir(dir: &str) {
+    let _ = SNAPSHOT_DIR.set(PathBuf::from(dir));
+}
+
+pub fn snapshot_dir() -> PathBuf {
+    SNAPSHOT_DIR.get().cloned().unwrap_or_else(|| get_application_cache_dir().join("snapshot"))
+}
+
+/// Snapshot name of the /dependencies response for `boot_version`
+pub fn dependencies_for(boot_version: &str) -> String {
+    format!("{}-{}", DEPENDENCIES, boot_version)
+}
+
+/// Keep `body` as the snapshot `name`. Best effort, a read-only cache must not fail requests.
+pub fn save(name: &str, body: &str) {
+    let dir = snapshot_dir();
+    if fs::create_dir_all(&dir).is_ok() {
+        let _ = fs::write(dir.join(format!("{}.json", name)), body);
+    }
+}
+
+/// Body of the snapshot `name`
+pub fn load(name: &str) -> Result<String, Box<dyn Error>> {
+    let dir = snapshot_dir();
+    fs::read_to_string(dir.join(format!("{}.json", name))).map_err(|_| {
+        format!(
+            "No {} snapshot in {}, run `spring-tui snapshot` where Initializr is reachable and copy the directory",
+            name,
+            dir.display()
+        )
+        .into()
+    })
+}
+
+/// Whether `error` means Initializr could not be reached at all, as opposed to an error response
+pub fn is_unreachable(error: &(dyn Error + 'static)) -> bool {
+    error.downcast_ref::<reqwest::Error>().is_some_and(|e| e.is_connect() || e.is_timeout())
+}
@@ -0,0 +1,205 @@
+// HTTP transport behind api, generator and the mirror: every GET to Initializr goes through the
+// Transport installed with set_transport, reqwest by default. Fixtures replays responses kept on
+// disk, or records them while passing requests to another transport, so the test suite can run
+// without start.spring.io.
+use crate::types::generic::ErrorResponse;
+use std::error::Error;
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+
+pub type TransportError = Box<dyn Error + Send + Sync>;
+pub type ResponseFuture<'a> = Pin<Box<dyn Future<Output = Result<HttpResponse, TransportError>> + Send + 'a>>;
+
+/// Set to record fixtures from the network instead of replaying them, see Fixtures::from_env
+pub const RECORD_ENV: &str = "SPRING_TUI_RECORD_FIXTURES";
+
+const USER_AGENT: &str = "spring-tui/0.0.1";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: String,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// The body of a 2xx response, otherwise the Initializr error message
+    pub fn into_success(self) -> Result<Vec<u8>, Box<dyn Error>> {
+        if (200..300).contains(&self.status) {
+            return Ok(self.body);
+        }
+        let error_text = String::from_utf8_lossy(&self.body).to_string();
+        match serde_json::from_str::<ErrorResponse>(&error_text) {
+            Ok(err) => Err(format!("Download failed: {}", err.message).into()),
+            Err(_) => Err(format!("Download failed: {}", error_text).into()),
+        }
+    }
+}
+
+pub trait Transport: Send + Sync {
+    /// GET `url`, with `accept` as the Accept header when given
+    fn get<'a>(&'a self, url: &'a str, accept: Option<&'a str>) -> ResponseFuture<'a>;
+}
+
+/// The network, through reqwest
+#[derive(Default)]
+pub struct Http {
+    client: reqwest::Client,
+}
+
+impl Transport for Http {
+    fn get<'a>(&'a self, url: &'a str, accept: Option<&'a str>) -> ResponseFuture<'a> {
+        Box::pin(async move {
+            let mut request = self.client.get(url).header(reqwest::header::USER_AGENT, USER_AGENT);
+            if let Some(accept) = accept {
+                request = request.header(reqwest::header::ACCEPT, accept);
+            }
+            let response = request.send().await?;
+            let status = response.status().as_u16();
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("application/octet-stream")
+                .to_string();
+            Ok(HttpResponse { status, content_type, body: response.bytes().await?.to_vec() })
+        })
+    }
+}
+
+/// Responses kept in `dir`, one `<name>.json` (url, status, content type) and `<name>.body` per request
+pub struct Fixtures {
+    dir: PathBuf,
+    /// transport whose responses are recorded; None replays
+    record: Option<Box<dyn Transport>>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FixtureMeta {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    accept: Option<String>,
+    status: u16,
+    content_type: String,
+}
+
+impl Fixtures {
+    /// Answer from the fixtures in `dir`, failing on requests that were not recorded
+    pub fn replay(dir: impl Into<PathBuf>) -> Self {
+        Fixtures { dir: dir.into(), record: None }
+    }
+
+    /// Pass requests to `transport`, keeping every response in `dir`
+    pub fn record(dir: impl Into<PathBuf>, transport: Box<dyn Transport>) -> Self {
+        Fixtures { dir: dir.into(), record: Some(transport) }
+    }
+
+    /// Record from the network when RECORD_ENV is set, replay otherwise
+    #[allow(dead_code)]
+    pub fn from_env(dir: impl Into<PathBuf>) -> Self {
+        match std::env::var_os(RECORD_ENV) {
+            Some(_) => Fixtures::record(dir, Box::new(Http::default())),
+            None => Fixtures::replay(dir),
+        }
+    }
+
+    /// File name, without extension, of the fixture for a request: the last path segment for
+    /// readability, then a hash of the url and Accept header
+    pub fn name(url: &str, accept: Option<&str>) -> String {
+        let path = url.split('?').next().unwrap_or_default();
+        let path = path.split_once("://").map_or(path, |(_, rest)| rest.split_once('/').map_or("", |(_, path)| path));
+        let segment = path.trim_end_matches('/').rsplit('/').next().filter(|s| !s.is_empty()).unwrap_or("root");
+        let hash = crate::generator::lock::sha256_hex(format!("{}\n{}", url, accept.unwrap_or_default()).as_bytes());
+        format!("{}-{}", segment, &hash[..12])
+    }
+
+    fn load(&self, url: &str, accept: Option<&str>) -> Result<HttpResponse, TransportError> {
+        let name = Fixtures::name(url, accept);
+        let meta = fs::read_to_string(self.dir.join(format!("{}.json", name))).map_err(|_| {
+            format!("No fixture {} for {} in {}, record it with {}=1", name, url, self.dir.display(), RECORD_ENV)
+        })?;
+        let meta: FixtureMeta = serde_json::from_str(&meta)?;
+        let body = fs::read(self.dir.join(format!("{}.body", name)))?;
+        Ok(HttpResponse { status: meta.status, content_type: meta.content_type, body })
+    }
+
+    fn save(&self, url: &str, accept: Option<&str>, response: &HttpResponse) -> Result<(), TransportError> {
+        let name = Fixtures::name(url, accept);
+        let meta = FixtureMeta {
+            url: url.to_string(),
+            accept: accept.map(str::to_string),
+            status: response.status,
+            content_type: response.content_type.clone(),
+        };
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.dir.join(format!("{}.json", name)), serde_json::to_string_pretty(&meta)? + "\n")?;
+        fs::write(self.dir.join(format!("{}.body", name)), &response.body)?;
+        Ok(())
+    }
+}
+
+impl Transport for Fixtures {
+    fn get<'a>(&'a self, url: &'a str, accept: Option<&'a str>) -> ResponseFuture<'a> {
+        Box::pin(async move {
+            match &self.record {
+                None => self.load(url, accept),
+                Some(transport) => {
+                    let response = transport.get(url, accept).await?;
+                    self.save(url, accept, &response)?;
+                    Ok(response)
+                }
+            }
+        })
+    }
+}
+
+static TRANSPORT: OnceLock<Arc<dyn Transport>> = OnceLock::new();
+
+/// Send every request through `transport`; only the first call has an effect
+#[allow(dead_code)]
+pub fn set_transport(transport: Arc<dyn Transport>) {
+    let _ = TRANSPORT.set(transport);
+}
+
+pub fn transport() -> Arc<dyn Transport> {
+    TRANSPORT.get_or_init(|| Arc::new(Http::default())).clone()
+}
+
+/// GET `url` through the installed transport
+pub async fn get(url: &str, accept: Option<&str>) -> Result<HttpResponse, Box<dyn Error>> {
+    transport().get(url, accept).await.map_err(|e| e as Box<dyn Error>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Canned;
+
+    impl Transport for Canned {
+        fn get<'a>(&'a self, url: &'a str, _accept: Option<&'a str>) -> ResponseFuture<'a> {
+            Box::pin(async move {
+                Ok(HttpResponse { status: 200, content_type: "text/plain".to_string(), body: url.as_bytes().to_vec() })
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_responses_replay() {
+        let dir = std::env::temp_dir().join(format!("spring-tui-fixtures-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let url = "https://start.spring.io/starter.zip?type=maven-project&dependencies=web";
+
+        let recorded = Fixtures::record(&dir, Box::new(Canned)).get(url, None).await.unwrap();
+        let replay = Fixtures::replay(&dir);
+
+        assert_eq!(replay.get(url, None).await.unwrap(), recorded);
+        assert!(Fixtures::name(url, None).starts_with("starter.zip-"));
+        let missing = replay.get(url, Some("application/json")).await.unwrap_err().to_string();
+        assert!(missing.contains(RECORD_ENV), "{}", missing);
+    }
+}
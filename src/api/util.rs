@@ -1,18 +1,18 @@
-use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, USER_AGENT};
+use std::sync::OnceLock;
 
-pub fn get_headers() -> HeaderMap {
-    let mut headers = HeaderMap::new();
+/// Accept header of the capabilities and /dependencies requests
+pub const ACCEPT_JSON: &str = "application/vnd.initializr.v2.3+json";
 
-    headers.insert(
-        ACCEPT,
-        HeaderValue::from_static("application/vnd.initializr.v2.3+json"),
-    );
+const DEFAULT_BASE_URL: &str = "https://start.spring.io/";
 
-    headers.insert(USER_AGENT, HeaderValue::from_static("spring-tui/0.0.1"));
+static BASE_URL: OnceLock<String> = OnceLock::new();
 
-    headers
+/// Point every request at another Initializr instance; only the first call has an effect
+pub fn set_base_url(url: &str) {
+    let url = format!("{}/", url.trim_end_matches('/'));
+    let _ = BASE_URL.set(url);
 }
 
 pub fn get_base_url() -> String {
-    String::from("https://start.spring.io/")
+    BASE_URL.get().map(String::as_str).unwrap_or(DEFAULT_BASE_URL).to_string()
 }
@@ -1,6 +1,7 @@
-use clap::Parser;
+use crate::types::config::{ConfigKey, FileType, GlobalConfig};
+use clap::{Parser, Subcommand, ValueEnum};
 
-#[derive(Parser)]
+#[derive(Parser, Default)]
 #[command(
     name = "spring-tui",
     version = env!("CARGO_PKG_VERSION"),
@@ -16,12 +17,17 @@ pub struct Args {
         short,
         long,
         value_name = "path-to-config",
-        help = "Generate project from config file",
+        help = "Generate project from config file, - reads stdin",
         long_help = "Specify a configuration file in yaml, json, or toml format to automatically \
-                     generate a Spring Boot project with predefined settings"
+                     generate a Spring Boot project with predefined settings. \
+                     use - to read the config from stdin"
     )]
     pub file: Option<String>,
 
+    /// Format of the config file, detected from the extension or content when omitted
+    #[arg(long, global = true, value_enum, value_name = "FORMAT")]
+    pub format: Option<FileType>,
+
     // /// Directory path for downloading generated projects
     // #[arg(
     //     short,
@@ -42,19 +48,421 @@ pub struct Args {
                      only applies when generating from a config file (--file option)"
     )]
     pub extract: bool,
+
+    /// Show where each setting comes from
+    #[arg(short, long, help = "Print the source of every effective setting")]
+    pub verbose: bool,
+
+    #[command(flatten)]
+    pub defaults: Defaults,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Defaults for new projects, taking precedence over the global and `.spring-tui.*` configs
+#[derive(clap::Args, Default, Clone, Debug, PartialEq)]
+#[command(next_help_heading = "Project defaults")]
+pub struct Defaults {
+    /// Directory generated projects are written to
+    #[arg(long, value_name = "DIR")]
+    pub dir: Option<String>,
+    /// Project type, e.g. maven-project, gradle-project, gradle-project-kotlin
+    #[arg(long, value_name = "TYPE")]
+    pub project_type: Option<String>,
+    /// java, kotlin or groovy
+    #[arg(long, value_name = "LANGUAGE")]
+    pub language: Option<String>,
+    /// jar or war
+    #[arg(long, value_name = "PACKAGING")]
+    pub packaging: Option<String>,
+    /// properties or yaml
+    #[arg(long, value_name = "FORMAT")]
+    pub configuration_file_format: Option<String>,
+    #[arg(long, value_name = "VERSION")]
+    pub java_version: Option<i32>,
+    #[arg(long, value_name = "VERSION")]
+    pub boot_version: Option<String>,
+    #[arg(long, value_name = "GROUP")]
+    pub group_id: Option<String>,
+    /// Package prefix, the artifact id is appended to it
+    #[arg(long, value_name = "PACKAGE")]
+    pub package_base: Option<String>,
+    /// Project version, e.g. 0.0.1-SNAPSHOT
+    #[arg(long, value_name = "VERSION")]
+    pub project_version: Option<String>,
+    /// Comma separated dependency ids
+    #[arg(long, value_name = "IDS")]
+    pub dependencies: Option<String>,
+    #[arg(long, value_name = "ARTIFACT")]
+    pub artifact_id: Option<String>,
+    #[arg(long, value_name = "NAME")]
+    pub name: Option<String>,
+    #[arg(long, value_name = "TEXT")]
+    pub description: Option<String>,
+    /// Full package name, wins over --package-base
+    #[arg(long, value_name = "PACKAGE")]
+    pub package_name: Option<String>,
+    /// Initializr instance to talk to
+    #[arg(long, value_name = "URL")]
+    pub base_url: Option<String>,
+    /// Comma separated template overlays applied after extraction
+    #[arg(long, value_name = "OVERLAYS")]
+    pub overlays: Option<String>,
+    /// Comma separated directories with user recipes
+    #[arg(long, value_name = "DIRS")]
+    pub recipe_dirs: Option<String>,
+    /// Command run before the project is requested, reads the hook JSON on stdin
+    #[arg(long, value_name = "COMMAND")]
+    pub before_request_hook: Option<String>,
+    /// Command run after the archive is downloaded
+    #[arg(long, value_name = "COMMAND")]
+    pub after_download_hook: Option<String>,
+    /// Command run after the project is extracted
+    #[arg(long, value_name = "COMMAND")]
+    pub after_extract_hook: Option<String>,
+    /// Initialise a git repository in extracted projects
+    #[arg(long, value_name = "BOOL")]
+    pub git_init: Option<bool>,
+    /// Initial branch of that repository
+    #[arg(long, value_name = "BRANCH")]
+    pub git_branch: Option<String>,
+    /// Initial commit message, may use {{artifactId}} and the other config values
+    #[arg(long, value_name = "TEMPLATE")]
+    pub git_commit_message: Option<String>,
+    /// Remote registered as origin, may use the same placeholders
+    #[arg(long, value_name = "URL")]
+    pub git_remote: Option<String>,
+    /// Write .spring-tui.yaml into extracted projects (default true)
+    #[arg(long, value_name = "BOOL")]
+    pub provenance: Option<bool>,
+    /// Where projects come from: initializr, offline or auto (offline when Initializr is unreachable)
+    #[arg(long, value_name = "BACKEND")]
+    pub backend: Option<String>,
+    /// Initializr metadata snapshot read by the offline backend
+    #[arg(long, value_name = "DIR")]
+    pub snapshot_dir: Option<String>,
 }
 
+impl From<Defaults> for GlobalConfig {
+    fn from(d: Defaults) -> Self {
+        GlobalConfig {
+            dir: d.dir,
+            project_type: d.project_type,
+            language: d.language,
+            packaging: d.packaging,
+            configuration_file_format: d.configuration_file_format,
+            java_version: d.java_version,
+            boot_version: d.boot_version,
+            group_id: d.group_id,
+            package_base: d.package_base,
+            version: d.project_version,
+            dependencies: d.dependencies,
+            artifact_id: d.artifact_id,
+            name: d.name,
+            description: d.description,
+            package_name: d.package_name,
+            base_url: d.base_url,
+            overlays: d.overlays,
+            recipe_dirs: d.recipe_dirs,
+            before_request_hook: d.before_request_hook,
+            after_download_hook: d.after_download_hook,
+            after_extract_hook: d.after_extract_hook,
+            git_init: d.git_init,
+            git_branch: d.git_branch,
+            git_commit_message: d.git_commit_message,
+            git_remote: d.git_remote,
+            provenance: d.provenance,
+            backend: d.backend,
+            snapshot_dir: d.snapshot_dir,
+            application: None,
+        }
+    }
+}
+
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Build a config from an existing Maven or Gradle project, a start.spring.io share link or a spring init command
+    #[command(
+        long_about = "Read a pom.xml, build.gradle or build.gradle.kts and recover the project settings \
+                      and Initializr dependencies, parse a start.spring.io share link (https://start.spring.io/#!...) \
+                      or a `spring init ...` command line. \
+                      opens the result in the tui unless --output is given"
+    )]
+    Import {
+        /// Project directory, build file, share link or spring init command
+        #[arg(value_name = "path-or-link")]
+        path: String,
+
+        /// Write the recovered config to this file (yaml, json or toml) instead of opening the tui
+        #[arg(short, long, value_name = "path-to-config")]
+        output: Option<String>,
+    },
+
+    /// Convert a config file into another config format or an equivalent command line
+    #[command(
+        long_about = "Export a config file as yaml, json or toml, or as a curl, httpie (http) or \
+                      spring boot cli (spring init) command producing the same project. \
+                      prints to stdout unless --output is given"
+    )]
+    Export {
+        /// Config file (YAML, JSON, or TOML), - reads stdin
+        #[arg(value_name = "path-to-config")]
+        file: String,
+
+        /// Export target
+        #[arg(short, long, value_enum, default_value_t = ExportTarget::Curl)]
+        to: ExportTarget,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long, value_name = "path")]
+        output: Option<String>,
+    },
+
+    /// Manage config files
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Print the start.spring.io share link for a config file
+    Share {
+        /// Config file (YAML, JSON, or TOML), - reads stdin
+        #[arg(value_name = "path-to-config")]
+        file: String,
+    },
+
+    /// Generate several projects from a manifest or a directory of config files
+    #[command(
+        long_about = "Generate every project listed in a manifest (a `projects` list with optional shared \
+                      `defaults`) or every config file in a directory. each project lands in \
+                      <dir>/<artifactId>; exits non-zero when any project fails"
+    )]
+    Batch {
+        /// Manifest file or directory of config files
+        #[arg(value_name = "PATH")]
+        path: String,
+        /// Projects generated at the same time
+        #[arg(short, long, default_value_t = 4)]
+        jobs: usize,
+        /// Extract every project after download
+        #[arg(short = 'x', long)]
+        extract: bool,
+    },
+
+    /// Generate a project as a new module of an existing multi-module build
+    #[command(
+        long_about = "Generate a project into a subdirectory of a repository and register it in the parent \
+                      pom.xml <modules> or settings.gradle(.kts). the build system follows the parent, and the \
+                      group id, version and java version are taken from the parent build unless --no-inherit"
+    )]
+    AddModule {
+        /// Config file for the module, the project defaults are used when omitted
+        #[arg(short, long, value_name = "path-to-config")]
+        config: Option<String>,
+        /// Repository root holding the parent build
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        repo: String,
+        /// Module directory name, defaults to the artifact id
+        #[arg(long, value_name = "NAME")]
+        module: Option<String>,
+        /// Keep group id, version and java version of the config instead of the parent's
+        #[arg(long)]
+        no_inherit: bool,
+    },
+
+    /// Add Initializr dependencies to an existing Maven or Gradle project
+    #[command(
+        long_about = "Fetch the build file Initializr generates for the project's boot version with the extra \
+                      dependencies, and merge its dependencies, BOMs and repositories into the existing \
+                      pom.xml or build.gradle(.kts). the change is shown as a diff before it is written"
+    )]
+    AddDeps {
+        /// Comma separated dependency ids, e.g. data-jpa,postgresql
+        #[arg(value_name = "IDS", value_delimiter = ',', required = true)]
+        ids: Vec<String>,
+        /// Project directory or build file
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        project: String,
+        /// Write without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Show and apply the skeleton changes of a Spring Boot upgrade
+    #[command(
+        long_about = "Generate the project skeleton for the current and the target boot version, diff their \
+                      build, wrapper and config files and apply the chosen hunks to the project. without \
+                      --apply or --review the numbered hunks are only printed"
+    )]
+    Upgrade {
+        /// Target Spring Boot version
+        #[arg(long, value_name = "VERSION")]
+        to: String,
+        /// Current Spring Boot version, read from the build file when omitted
+        #[arg(long, value_name = "VERSION")]
+        from: Option<String>,
+        /// Project directory or build file
+        #[arg(long, value_name = "DIR", default_value = ".")]
+        project: String,
+        /// Hunks to apply: `all` or comma separated numbers from the printed diff
+        #[arg(long, value_name = "HUNKS", conflicts_with = "review")]
+        apply: Option<String>,
+        /// Pick the hunks in a diff view
+        #[arg(long)]
+        review: bool,
+    },
+
+    /// List the recipes applied to extracted projects and the dependencies triggering them
+    Recipes,
+
+    /// Save the Initializr metadata the offline backend needs
+    #[command(
+        long_about = "Fetch the capabilities and the dependency coordinates for every Spring Boot version into \
+                      the snapshot directory. copy it to machines without access to Initializr and point \
+                      snapshotDir at it there, with backend set to offline or auto"
+    )]
+    Snapshot,
+
+    /// Run a caching Initializr mirror for the team
+    #[command(
+        long_about = "Serve the Initializr API (capabilities, /dependencies, starter.zip, pom.xml, build.gradle) \
+                      over HTTP, proxying to the configured baseUrl and caching responses on disk. point \
+                      baseUrl at http://<host>:<port>/ on the other machines"
+    )]
+    Serve {
+        /// Address to listen on
+        #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:8080")]
+        bind: String,
+        /// Cache directory, <cache dir>/mirror by default
+        #[arg(long, value_name = "DIR")]
+        cache: Option<String>,
+        /// Seconds before a cached response is fetched again
+        #[arg(long, value_name = "SECONDS", default_value_t = 86400)]
+        max_age: u64,
+        /// Answer from the cache only, never contact upstream
+        #[arg(long)]
+        cache_only: bool,
+    },
+
+    /// Regenerate a project from the request recorded in its lock
+    #[command(
+        long_about = "Replay the request recorded in a spring-tui.lock (or <artifactId>.lock next to an archive), \
+                      then report whether the archive checksum and the dependency coordinates still match the lock. \
+                      Recipes and overlays are the ones installed now"
+    )]
+    Regenerate {
+        /// Lockfile to replay
+        #[arg(value_name = "LOCK", default_value = "spring-tui.lock")]
+        lock: String,
+        /// Extract the project after download
+        #[arg(short = 'x', long)]
+        extract: bool,
+    },
+
+    /// Generate a multi-module Maven or Gradle workspace
+    #[command(
+        long_about = "Generate every project of a manifest or directory as a module of one workspace, \
+                      then write an aggregator pom.xml or settings.gradle(.kts). the optional `workspace` \
+                      table of the manifest sets the aggregator groupId, artifactId, version and name"
+    )]
+    Workspace {
+        /// Manifest file or directory of config files
+        #[arg(value_name = "PATH")]
+        path: String,
+        /// Modules generated at the same time
+        #[arg(short, long, default_value_t = 4)]
+        jobs: usize,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+pub enum ConfigAction {
+    /// Show the effective project defaults and which file set each one
+    #[command(
+        long_about = "List the config layers that apply in the current directory (global config, \
+                      .spring-tui.{yaml,toml,json} files found walking up from here, command line flags) \
+                      and the layer each effective default comes from"
+    )]
+    Explain,
+
+    /// Print a value from the global config
+    Get {
+        /// Key, e.g. groupId or group-id
+        key: ConfigKey,
+    },
+
+    /// Set a value in the global config, creating the file if needed
+    Set {
+        /// Key, e.g. groupId or group-id
+        key: ConfigKey,
+        value: String,
+    },
+
+    /// Remove a value from the global config
+    Unset {
+        /// Key, e.g. groupId or group-id
+        key: ConfigKey,
+    },
+
+    /// List the global config file and every key in it
+    List,
+
+    /// Rewrite config files in the current format
+    #[command(
+        long_about = "Upgrade config files written by older spring-tui versions to the current \
+                      format (configVersion). files are rewritten in place, keeping their yaml/json/toml format"
+    )]
+    Migrate {
+        /// Config files (YAML, JSON, or TOML)
+        #[arg(value_name = "path-to-config", required = true)]
+        files: Vec<String>,
+    },
+    /// Convert a project config to another format
+    #[command(
+        long_about = "Convert a project config between yaml, json and toml. the output format comes from \
+                      the output file extension, keys keep their order and dependency lists \
+                      are trimmed and deduplicated"
+    )]
+    Convert {
+        /// Config to read, - reads stdin
+        #[arg(value_name = "IN")]
+        input: String,
+        /// File to write, e.g. config.toml
+        #[arg(value_name = "OUT")]
+        output: String,
+        /// Take keys missing from the input from the Initializr defaults
+        #[arg(long)]
+        fill_defaults: bool,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportTarget {
+    Yaml,
+    Json,
+    Toml,
+    Curl,
+    #[value(name = "http")]
+    Httpie,
+    #[value(name = "spring")]
+    SpringInit,
+}
 
 #[derive(Default)]
 pub struct CliResult {
     pub file: Option<String>,
     pub extract: Option<bool>,
+    pub command: Option<Command>,
+    pub defaults: Defaults,
+    pub verbose: bool,
+    pub format: Option<FileType>,
 }
 
 pub fn parse() -> Option<CliResult> {
     let args = Args::parse();
 
-    if args.file.is_none() {
+    if args.file.is_none() && args.command.is_none() && args.defaults == Defaults::default() && !args.verbose {
         return None;
     }
 
@@ -63,6 +471,10 @@ pub fn parse() -> Option<CliResult> {
     Some(CliResult {
         file: args.file,
         extract,
+        command: args.command,
+        defaults: args.defaults,
+        verbose: args.verbose,
+        format: args.format,
     })
 }
 
@@ -2,10 +2,23 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
-use crate::config::util;
+use crate::config::{migrate, util};
 use serde::{Deserialize, Serialize};
+use crate::types::config::FileType;
 use crate::types::generic::SprintInitConfig;
 
-pub fn parse_config(path: String) -> anyhow::Result<SprintInitConfig> {
-    util::parse_config(path.as_str())
+/// Parse a project config, migrating files written by older spring-tui versions
+pub fn parse_config(path: String, format: Option<FileType>) -> anyhow::Result<SprintInitConfig> {
+    let loaded = migrate::load(path.as_str(), format)?;
+    if let Some(version) = loaded.migrated_from {
+        if path == util::STDIN {
+            eprintln!("\x1b[33m! stdin uses config format v{}, consider upgrading the producer\x1b[0m", version);
+            return Ok(loaded.config);
+        }
+        eprintln!(
+            "\x1b[33m! {} uses config format v{}, run `spring-tui config migrate {}` to update it\x1b[0m",
+            path, version, path
+        );
+    }
+    Ok(loaded.config)
 }
@@ -0,0 +1,78 @@
+// converts project configs between yaml, json and toml. The input is validated (and migrated)
+// through SprintInitConfig, but keys come out in the order the input listed them; keys it didn't
+// have (new in this version, or filled from defaults) follow in field order.
+use crate::config::migrate;
+use crate::types::config::FileType;
+use crate::types::generic::{normalize_dependencies, SprintInitConfig};
+use serde_json::{Map, Value};
+
+/// Convert a config document from `from` to `to`. Keys missing from the input are taken from
+/// `defaults` when given, otherwise they are an error.
+pub fn convert(
+    content: &str,
+    from: FileType,
+    to: FileType,
+    defaults: Option<&SprintInitConfig>,
+) -> anyhow::Result<String> {
+    let mut value = migrate::parse_value(content, &from)?;
+    let order: Vec<String> = match &value {
+        Value::Object(map) => map.keys().cloned().collect(),
+        _ => Vec::new(),
+    };
+
+    if let (Some(defaults), Value::Object(map)) = (defaults, &mut value) {
+        let Value::Object(defaults) = serde_json::to_value(defaults)? else { unreachable!() };
+        for (key, default) in defaults {
+            map.entry(key).or_insert(default);
+        }
+    }
+
+    let mut config = migrate::migrate(value)
+        .map_err(|e| anyhow::anyhow!("{} (use --fill-defaults to take missing keys from Initializr)", e))?
+        .config;
+    config.dependencies = normalize_dependencies(&config.dependencies);
+
+    let Value::Object(mut fields) = serde_json::to_value(&config)? else { unreachable!() };
+    let mut ordered = Map::new();
+    for key in &order {
+        if let Some(value) = fields.shift_remove(key) {
+            ordered.insert(key.clone(), value);
+        }
+    }
+    ordered.extend(fields);
+    render(&Value::Object(ordered), &to)
+}
+
+fn render(value: &Value, to: &FileType) -> anyhow::Result<String> {
+    Ok(match to {
+        FileType::Yaml => serde_yaml::to_string(value)?,
+        FileType::Json => serde_json::to_string_pretty(value)?,
+        FileType::Toml => toml::to_string_pretty(value)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_yaml_to_toml_in_input_order() {
+        let yaml = "dependencies: 'web, lombok,,web'\nprojectType: gradle-project\n";
+
+        let toml = convert(yaml, FileType::Yaml, FileType::Toml, Some(&SprintInitConfig::default())).unwrap();
+
+        assert!(toml.starts_with("dependencies = \"web,lombok\"\nprojectType = \"gradle-project\"\nconfigVersion = 2\n"), "{}", toml);
+
+        // toml wants plain keys before tables, whatever the input order
+        let nested = "application:\n  serverPort: 8081\nartifactId: orders\n";
+        let toml = convert(nested, FileType::Yaml, FileType::Toml, Some(&SprintInitConfig::default())).unwrap();
+        assert!(toml.starts_with("artifactId = \"orders\"\n") && toml.contains("[application]\nserverPort = 8081\n"), "{}", toml);
+    }
+
+    #[test]
+    fn missing_keys_need_defaults() {
+        let err = convert("{}", FileType::Json, FileType::Yaml, None).unwrap_err();
+
+        assert!(err.to_string().contains("--fill-defaults"));
+    }
+}
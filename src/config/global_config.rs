@@ -5,17 +5,58 @@
 use crate::config::util;
 use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf};
-use crate::types::config::GlobalConfig;
+use crate::types::config::{ConfigKey, ConfigMetadata, FileType, GlobalConfig};
 
-/// Read GlobalConfig from config file
+/// Read GlobalConfig from config file, defaults when there is none yet
 pub fn get_config() -> anyhow::Result<GlobalConfig> {
-    let path = match util::get_application_config_path() {
-        Some(data) => data,
-        _ => anyhow::bail!("fuck"),
+    match util::get_application_config_path() {
+        Some(path) => util::parse_config(path.as_str()),
+        None => Ok(GlobalConfig::default()),
+    }
+}
+
+/// The user's own config file and its contents, which is what gets edited
+fn writable_config() -> anyhow::Result<(PathBuf, GlobalConfig)> {
+    let path = util::get_writable_config_path();
+    let config = if path.exists() {
+        util::parse_config(&path.to_string_lossy())?
+    } else {
+        GlobalConfig::default()
+    };
+    Ok((path, config))
+}
+
+/// set the global config ( overwrite or edit variables ), creating the file on first write.
+/// an existing file keeps its format
+pub fn set(config: &GlobalConfig) -> anyhow::Result<PathBuf> {
+    let path = util::get_writable_config_path();
+    let metadata = ConfigMetadata::from_path(path.to_string_lossy().to_string())?;
+
+    let content = match metadata.file_type {
+        FileType::Yaml => serde_yaml::to_string(config)?,
+        FileType::Json => serde_json::to_string_pretty(config)?,
+        FileType::Toml => toml::to_string_pretty(config)?,
     };
 
-    util::parse_config(path.as_str())
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, content)?;
+    Ok(path)
 }
 
-/// set the global config ( overwrite or edit variables )
-pub fn set(config: &GlobalConfig) {}
+pub fn get_value(key: ConfigKey) -> anyhow::Result<Option<String>> {
+    Ok(key.get(&get_config()?))
+}
+
+pub fn set_value(key: ConfigKey, value: &str) -> anyhow::Result<PathBuf> {
+    let (_, mut config) = writable_config()?;
+    key.set(&mut config, value)?;
+    set(&config)
+}
+
+pub fn unset_value(key: ConfigKey) -> anyhow::Result<PathBuf> {
+    let (_, mut config) = writable_config()?;
+    key.unset(&mut config);
+    set(&config)
+}
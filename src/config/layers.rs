@@ -0,0 +1,264 @@
+// effective defaults are built from layers, later layers win:
+//   global config  <  .spring-tui.* in parent dirs  <  .spring-tui.* in the working dir
+//   <  SPRING_TUI_* environment variables  <  cli flags
+use crate::config::util;
+use crate::generator::provenance;
+use crate::types::config::{ConfigKey, GlobalConfig};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+const DIRECTORY_CONFIG_NAMES: &[&str] = &[".spring-tui.yaml", ".spring-tui.yml", ".spring-tui.toml", ".spring-tui.json"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Global(PathBuf),
+    Directory(PathBuf),
+    /// environment variable name
+    Env(String),
+    Cli,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Global(path) => write!(f, "global config {}", path.display()),
+            Source::Directory(path) => write!(f, "{}", path.display()),
+            Source::Env(var) => write!(f, "environment {}", var),
+            Source::Cli => write!(f, "command line flag"),
+        }
+    }
+}
+
+pub struct Layer {
+    pub source: Source,
+    pub values: GlobalConfig,
+}
+
+pub struct Resolved {
+    pub values: GlobalConfig,
+    /// every layer consulted, lowest precedence first
+    pub layers: Vec<Source>,
+    /// layer that set each key, by camelCase key name
+    pub sources: BTreeMap<String, Source>,
+    /// layers left out because they could not be read
+    pub warnings: Vec<String>,
+}
+
+/// `.spring-tui.*` files from the filesystem root down to `dir`, one per directory
+pub fn discover(dir: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = dir
+        .ancestors()
+        .filter_map(|d| DIRECTORY_CONFIG_NAMES.iter().map(|n| d.join(n)).find(|p| p.is_file()))
+        .collect();
+    found.reverse();
+    found
+}
+
+/// A generated project's provenance file counts as the settings it records
+fn read_layer(path: PathBuf, source: impl FnOnce(PathBuf) -> Source) -> anyhow::Result<Layer> {
+    let path_str = path.to_string_lossy().to_string();
+    let values = match std::fs::read_to_string(&path) {
+        Ok(content) if provenance::is_provenance(&content) => provenance::parse(&content).map(|c| GlobalConfig::from_project(&c)),
+        _ => util::parse_config(&path_str),
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    Ok(Layer { source: source(path), values })
+}
+
+/// Layer of the SPRING_TUI_* variable for `key`, validated like `config set`
+fn env_layer(key: ConfigKey, lookup: &impl Fn(&str) -> Option<String>) -> anyhow::Result<Option<Layer>> {
+    let var = key.env_var();
+    let Some(value) = lookup(&var) else { return Ok(None) };
+    let mut values = GlobalConfig::default();
+    key.set(&mut values, &value).map_err(|e| anyhow::anyhow!("{}: {}", var, e))?;
+    Ok(Some(Layer { source: Source::Env(var), values }))
+}
+
+/// One layer per SPRING_TUI_* variable `lookup` knows about
+pub fn env_layers(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Vec<Layer>> {
+    let mut layers = Vec::new();
+    for key in ConfigKey::ALL {
+        layers.extend(env_layer(key, &lookup)?);
+    }
+    Ok(layers)
+}
+
+fn process_env(var: &str) -> Option<String> {
+    std::env::var(var).ok().filter(|v| !v.is_empty())
+}
+
+/// Collect every layer that applies in `dir`, lowest precedence first. A file or variable that
+/// can't be read is left out with a warning, so a broken setting never locks out `config`.
+pub fn layers(dir: &Path, cli: GlobalConfig, lookup: impl Fn(&str) -> Option<String>) -> (Vec<Layer>, Vec<String>) {
+    let mut read = Vec::new();
+    if let Some(global) = util::get_application_config_path() {
+        read.push(read_layer(PathBuf::from(global), Source::Global));
+    }
+    for path in discover(dir) {
+        read.push(read_layer(path, Source::Directory));
+    }
+    read.extend(ConfigKey::ALL.into_iter().filter_map(|key| env_layer(key, &lookup).transpose()));
+
+    let mut layers = Vec::new();
+    let mut warnings = Vec::new();
+    for layer in read {
+        match layer {
+            Ok(layer) => layers.push(layer),
+            Err(e) => warnings.push(format!("{}, ignoring it", e)),
+        }
+    }
+    layers.push(Layer { source: Source::Cli, values: cli });
+    (layers, warnings)
+}
+
+pub fn merge(layers: Vec<Layer>) -> anyhow::Result<Resolved> {
+    let mut merged = Map::new();
+    let mut sources = BTreeMap::new();
+    let consulted = layers.iter().map(|l| l.source.clone()).collect();
+
+    for layer in layers {
+        let Value::Object(values) = serde_json::to_value(&layer.values)? else { continue };
+        for (key, value) in values {
+            sources.insert(key.clone(), layer.source.clone());
+            merged.insert(key, value);
+        }
+    }
+
+    Ok(Resolved {
+        values: serde_json::from_value(Value::Object(merged))?,
+        layers: consulted,
+        sources,
+        warnings: Vec::new(),
+    })
+}
+
+/// Effective defaults for the working directory `dir`
+pub fn resolve(dir: &Path, cli: GlobalConfig) -> anyhow::Result<Resolved> {
+    let (layers, warnings) = layers(dir, cli, process_env);
+    Ok(Resolved { warnings, ..merge(layers)? })
+}
+
+/// Environment and cli settings only; these win over the values of an explicit config file.
+/// A bad SPRING_TUI_* value becomes a warning, as in `resolve`.
+pub fn overrides(cli: GlobalConfig) -> anyhow::Result<Resolved> {
+    overrides_from(cli, process_env)
+}
+
+fn overrides_from(cli: GlobalConfig, lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Resolved> {
+    let mut layers = Vec::new();
+    let mut warnings = Vec::new();
+    for key in ConfigKey::ALL {
+        match env_layer(key, &lookup) {
+            Ok(layer) => layers.extend(layer),
+            Err(e) => warnings.push(format!("{}, ignoring it", e)),
+        }
+    }
+    layers.push(Layer { source: Source::Cli, values: cli });
+    Ok(Resolved { warnings, ..merge(layers)? })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn nearest_directory_config_wins() {
+        let root = std::env::temp_dir().join(format!("spring-tui-layers-{}", std::process::id()));
+        let domain = root.join("payments");
+        let service = domain.join("ledger");
+        fs::create_dir_all(&service).unwrap();
+        fs::write(root.join(".spring-tui.toml"), "groupId = \"com.acme\"\njavaVersion = 17\n").unwrap();
+        fs::write(domain.join(".spring-tui.yaml"), "groupId: com.acme.payments\n").unwrap();
+
+        let found = discover(&service);
+        assert_eq!(found.len(), 2);
+
+        let layers = found
+            .into_iter()
+            .map(|p| read_layer(p, Source::Directory).unwrap())
+            .chain([Layer {
+                source: Source::Cli,
+                values: GlobalConfig { java_version: Some(21), ..Default::default() },
+            }])
+            .collect();
+        let resolved = merge(layers).unwrap();
+
+        assert_eq!(resolved.values.group_id.as_deref(), Some("com.acme.payments"));
+        assert_eq!(resolved.values.java_version, Some(21));
+        assert_eq!(resolved.sources["groupId"], Source::Directory(domain.join(".spring-tui.yaml")));
+        assert_eq!(resolved.sources["javaVersion"], Source::Cli);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn env_sits_between_files_and_cli() {
+        let env = |var: &str| match var {
+            "SPRING_TUI_GROUP_ID" => Some("com.env".to_string()),
+            "SPRING_TUI_JAVA_VERSION" => Some("17".to_string()),
+            _ => None,
+        };
+        let mut layers = vec![Layer {
+            source: Source::Directory(PathBuf::from(".spring-tui.yaml")),
+            values: GlobalConfig { group_id: Some("com.file".to_string()), ..Default::default() },
+        }];
+        layers.extend(env_layers(env).unwrap());
+        layers.push(Layer {
+            source: Source::Cli,
+            values: GlobalConfig { java_version: Some(21), ..Default::default() },
+        });
+        let resolved = merge(layers).unwrap();
+
+        assert_eq!(resolved.values.group_id.as_deref(), Some("com.env"));
+        assert_eq!(resolved.sources["groupId"], Source::Env("SPRING_TUI_GROUP_ID".to_string()));
+        assert_eq!(resolved.values.java_version, Some(21));
+    }
+
+    #[test]
+    fn invalid_env_values_name_the_variable() {
+        let env = |var: &str| (var == "SPRING_TUI_JAVA_VERSION").then(|| "latest".to_string());
+
+        let err = env_layers(env).err().unwrap().to_string();
+
+        assert!(err.starts_with("SPRING_TUI_JAVA_VERSION:"), "{}", err);
+    }
+
+    #[test]
+    fn unreadable_layers_become_warnings() {
+        let dir = std::env::temp_dir().join(format!("spring-tui-broken-layer-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(".spring-tui.toml"), "groupId = [unterminated\n").unwrap();
+        let env = |var: &str| match var {
+            "SPRING_TUI_JAVA_VERSION" => Some("latest".to_string()),
+            "SPRING_TUI_ARTIFACT_ID" => Some("orders".to_string()),
+            _ => None,
+        };
+
+        let (layers, warnings) = layers(&dir, GlobalConfig::default(), env);
+        let resolved = merge(layers).unwrap();
+
+        assert_eq!(resolved.values.artifact_id.as_deref(), Some("orders"));
+        assert!(warnings.iter().any(|w| w.contains(".spring-tui.toml")), "{:?}", warnings);
+        assert!(warnings.iter().any(|w| w.starts_with("SPRING_TUI_JAVA_VERSION:")), "{:?}", warnings);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn overrides_warn_about_invalid_env_values() {
+        let env = |var: &str| match var {
+            "SPRING_TUI_JAVA_VERSION" => Some("latest".to_string()),
+            "SPRING_TUI_GROUP_ID" => Some("com.env".to_string()),
+            _ => None,
+        };
+
+        let resolved = overrides_from(GlobalConfig::default(), env).unwrap();
+
+        assert_eq!(resolved.values.group_id.as_deref(), Some("com.env"));
+        assert_eq!(resolved.values.java_version, None);
+        assert_eq!(resolved.warnings.len(), 1);
+        assert!(resolved.warnings[0].starts_with("SPRING_TUI_JAVA_VERSION:"), "{:?}", resolved.warnings);
+    }
+}
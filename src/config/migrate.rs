@@ -0,0 +1,177 @@
+// config files carry a `configVersion`; files written before it existed are version 1.
+// every format change adds a step to MIGRATIONS and bumps types::generic::CONFIG_VERSION
+use crate::config::util;
+use crate::types::config::{ConfigMetadata, FileType};
+use crate::types::generic::{SprintInitConfig, CONFIG_VERSION};
+use serde_json::{Map, Value};
+use std::fs;
+
+/// Version assumed for files without a `configVersion` key
+const UNVERSIONED: u32 = 1;
+
+pub struct Migration {
+    /// version this step upgrades from, to `from + 1`
+    pub from: u32,
+    pub description: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: 1,
+    description: "drop the unused platformVersion key",
+    apply: drop_platform_version,
+}];
+
+fn drop_platform_version(config: &mut Map<String, Value>) {
+    let platform_version = config.remove("platformVersion");
+    let boot_version_missing = config
+        .get("bootVersion")
+        .and_then(Value::as_str)
+        .is_none_or(str::is_empty);
+
+    // the web ui calls the boot version "platformVersion", keep it if that is all we have
+    if boot_version_missing
+        && let Some(Value::String(v)) = platform_version
+        && !v.is_empty()
+    {
+        config.insert("bootVersion".to_string(), Value::String(v));
+    }
+}
+
+/// Steps needed to bring a file written in `version` up to CONFIG_VERSION
+pub fn steps_since(version: u32) -> impl Iterator<Item = &'static Migration> {
+    MIGRATIONS.iter().filter(move |m| m.from >= version)
+}
+
+pub struct LoadedConfig {
+    pub config: SprintInitConfig,
+    /// version the file was written in, when older than CONFIG_VERSION
+    pub migrated_from: Option<u32>,
+}
+
+pub fn parse_value(content: &str, file_type: &FileType) -> anyhow::Result<Value> {
+    Ok(match file_type {
+        FileType::Yaml => serde_yaml::from_str(content)?,
+        FileType::Json => serde_json::from_str(content)?,
+        FileType::Toml => toml::from_str(content)?,
+    })
+}
+
+/// Bring a raw config document up to CONFIG_VERSION and deserialize it
+pub fn migrate(value: Value) -> anyhow::Result<LoadedConfig> {
+    let Value::Object(mut map) = value else {
+        anyhow::bail!("Config must be a table of settings");
+    };
+
+    let version = match map.get("configVersion") {
+        None => UNVERSIONED,
+        Some(v) => v
+            .as_u64()
+            .map(|v| v as u32)
+            .ok_or_else(|| anyhow::anyhow!("configVersion must be a number"))?,
+    };
+    if version > CONFIG_VERSION {
+        anyhow::bail!(
+            "Config format version {} is newer than this spring-tui supports ({}), please upgrade",
+            version,
+            CONFIG_VERSION
+        );
+    }
+
+    for step in steps_since(version) {
+        (step.apply)(&mut map);
+    }
+    map.insert("configVersion".to_string(), Value::from(CONFIG_VERSION));
+
+    Ok(LoadedConfig {
+        config: serde_json::from_value(Value::Object(map))?,
+        migrated_from: (version < CONFIG_VERSION).then_some(version),
+    })
+}
+
+/// Load the config at `path` (`-` for stdin), in `format` or the detected one
+pub fn load(path: &str, format: Option<FileType>) -> anyhow::Result<LoadedConfig> {
+    let content = util::read_source(path)?;
+    let metadata = ConfigMetadata::detect(path, &content, format);
+    migrate(parse_value(&content, &metadata.file_type)?)
+}
+
+/// Rewrite `path` in the current format, keeping its file type.
+/// Returns the version the file was migrated from, or None when it was already current.
+pub fn migrate_file(path: &str) -> anyhow::Result<Option<u32>> {
+    if path == util::STDIN {
+        anyhow::bail!("Cannot migrate stdin in place, pipe it through `spring-tui export - --to yaml` instead");
+    }
+    let content = fs::read_to_string(path)?;
+    let metadata = ConfigMetadata::detect(path, &content, None);
+    let loaded = migrate(parse_value(&content, &metadata.file_type)?)?;
+    if loaded.migrated_from.is_some() {
+        let content = crate::generator::render_config(&loaded.config, &metadata.file_type)
+            .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        fs::write(path, content)?;
+    }
+    Ok(loaded.migrated_from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1_YAML: &str = r#"
+projectType: maven-project
+language: kotlin
+platformVersion: ""
+packaging: jar
+configurationFileFormat: properties
+javaVersion: 21
+groupId: org.turntabl
+artifactId: rake
+name: rakeservice
+description: Demo Rake Service
+packageName: org.turntabl.rake
+dependencies: security,web
+bootVersion: 3.5.10
+version: 0.0.1-SNAPSHOT
+"#;
+
+    #[test]
+    fn migrates_unversioned_files() {
+        let loaded = migrate(parse_value(V1_YAML, &FileType::Yaml).unwrap()).unwrap();
+
+        assert_eq!(loaded.migrated_from, Some(1));
+        assert_eq!(loaded.config.config_version, CONFIG_VERSION);
+        assert_eq!(loaded.config.boot_version, "3.5.10");
+    }
+
+    #[test]
+    fn platform_version_fills_missing_boot_version() {
+        let json = r#"{ "platformVersion": "3.4.1", "bootVersion": "" }"#;
+        let mut map = match serde_json::from_str(json).unwrap() {
+            Value::Object(map) => map,
+            _ => unreachable!(),
+        };
+
+        drop_platform_version(&mut map);
+
+        assert_eq!(map.get("bootVersion"), Some(&Value::from("3.4.1")));
+        assert!(!map.contains_key("platformVersion"));
+    }
+
+    #[test]
+    fn current_files_are_left_alone() {
+        let toml = crate::generator::render_config(&SprintInitConfig::default(), &FileType::Toml).unwrap();
+
+        let loaded = migrate(parse_value(&toml, &FileType::Toml).unwrap()).unwrap();
+
+        assert!(toml.starts_with("configVersion = 2"));
+        assert_eq!(loaded.migrated_from, None);
+        assert_eq!(loaded.config, SprintInitConfig::default());
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let value = serde_json::json!({ "configVersion": CONFIG_VERSION + 1 });
+
+        assert!(migrate(value).is_err());
+    }
+}
@@ -1,11 +1,42 @@
 mod config_parser;
-mod global_config;
-mod util;
+pub mod convert;
+pub mod global_config;
+pub mod layers;
+pub mod migrate;
+pub mod util;
 use crate::generator;
+use crate::generator::git::GitOptions;
+use crate::generator::hooks::HookCommands;
+use crate::generator::Generation;
+use crate::types::config::{FileType, GlobalConfig};
+use crate::types::generic::SprintInitConfig;
+use std::path::Path;
 
-pub async fn create_project_from_config(path: &str, extract: bool) -> anyhow::Result<()> {
-    let config = config_parser::parse_config(path.to_string())?;
-    generator::generate_project(&config, extract)
+/// Load a project config; `path` may be `-` for stdin, `format` overrides detection
+pub fn load_config(path: &str, format: Option<FileType>) -> anyhow::Result<SprintInitConfig> {
+    config_parser::parse_config(path.to_string(), format)
+}
+
+/// Generate the project described by the config file at `path` into the working directory
+pub async fn create_project_from_config(path: &str, extract: bool) -> anyhow::Result<Generation> {
+    create_project_from_config_in(path, None, extract, Path::new("."), &GlobalConfig::default(), None, None).await
+}
+
+/// Generate the project described by the config file at `path` into `dir`,
+/// with `overrides` (environment and cli settings) applied on top of the file.
+/// `git` turns the extracted project into a repository, `hooks` run at each stage.
+pub async fn create_project_from_config_in(
+    path: &str,
+    format: Option<FileType>,
+    extract: bool,
+    dir: &Path,
+    overrides: &GlobalConfig,
+    git: Option<&GitOptions>,
+    hooks: Option<&HookCommands>,
+) -> anyhow::Result<Generation> {
+    let mut config = config_parser::parse_config(path.to_string(), format)?;
+    overrides.apply_to(&mut config);
+    generator::generate_project_into(&config, extract, dir, git, hooks)
         .await
         .map_err(|e| anyhow::anyhow!(e.to_string()))
 }
@@ -1,11 +1,29 @@
 use serde::de::DeserializeOwned;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use crate::types::config::{ConfigMetadata, FileType};
 
+/// Config path meaning "read from stdin"
+pub const STDIN: &str = "-";
+
+/// Content of the config at `path`, or of stdin when `path` is `-`.
+/// An archive generated by spring-tui stands for the config in its comment.
+pub fn read_source(path: &str) -> anyhow::Result<String> {
+    if path == STDIN {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        return Ok(content);
+    }
+    if path.ends_with(".zip") {
+        return crate::generator::provenance::archive_comment(Path::new(path));
+    }
+    Ok(fs::read_to_string(path)?)
+}
+
 pub fn parse_config<T: DeserializeOwned>(path: &str) -> anyhow::Result<T> {
-    let content = fs::read_to_string(path)?;
-    let metadata = ConfigMetadata::from_path(path.to_string())?;
+    let content = read_source(path)?;
+    let metadata = ConfigMetadata::detect(path, &content, None);
 
     match metadata.file_type {
         FileType::Yaml => Ok(serde_yaml::from_str(&content)?),
@@ -14,16 +32,60 @@ pub fn parse_config<T: DeserializeOwned>(path: &str) -> anyhow::Result<T> {
     }
 }
 
+const CONFIG_EXTENSIONS: [&str; 4] = ["json", "toml", "yml", "yaml"];
+
+/// Absolute directory from an XDG variable; relative values must be ignored per the spec
+fn xdg_dir(value: Option<String>) -> Option<PathBuf> {
+    value.map(PathBuf::from).filter(|p| p.is_absolute())
+}
+
+/// User config directory: $XDG_CONFIG_HOME/spring-tui, falling back to ~/.config/spring-tui
+pub fn get_application_config_dir() -> PathBuf {
+    xdg_dir(std::env::var("XDG_CONFIG_HOME").ok())
+        .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+        .or_else(dirs::config_dir)
+        .unwrap_or_else(|| PathBuf::from(".config"))
+        .join("spring-tui")
+}
+
+/// User cache directory: $XDG_CACHE_HOME/spring-tui, falling back to ~/.cache/spring-tui
+pub fn get_application_cache_dir() -> PathBuf {
+    xdg_dir(std::env::var("XDG_CACHE_HOME").ok())
+        .or_else(|| dirs::home_dir().map(|h| h.join(".cache")))
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(|| PathBuf::from(".cache"))
+        .join("spring-tui")
+}
+
+/// System config directories from $XDG_CONFIG_DIRS (default /etc/xdg), most important first
+fn system_config_dirs() -> Vec<PathBuf> {
+    let dirs = std::env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_string());
+    dirs.split(':')
+        .filter_map(|d| xdg_dir(Some(d.to_string())))
+        .map(|d| d.join("spring-tui"))
+        .collect()
+}
+
+fn find_config_in(dir: &Path) -> Option<PathBuf> {
+    CONFIG_EXTENSIONS
+        .iter()
+        .map(|ext| dir.join(format!("config.{}", ext)))
+        .find(|p| p.exists())
+}
+
+/// Existing global config file, the user's own before any system wide one
 pub fn get_application_config_path() -> Option<String> {
-    let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
-    let config_dir = PathBuf::from(home).join(".config/spring-tui");
-
-    for ext in ["json", "toml", "yml", "yaml"] {
-        let path = config_dir.join(format!("config.{}", ext));
-        if path.exists() {
-            return path.to_str().map(String::from);
-        }
-    }
+    std::iter::once(get_application_config_dir())
+        .chain(system_config_dirs())
+        .find_map(|dir| find_config_in(&dir))
+        .and_then(|p| p.to_str().map(String::from))
+}
 
-    None
+/// Global config file to write to: the user's existing one, or a new config.toml
+pub fn get_writable_config_path() -> PathBuf {
+    let dir = get_application_config_dir();
+    find_config_in(&dir).unwrap_or_else(|| dir.join("config.toml"))
 }
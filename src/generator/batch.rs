@@ -0,0 +1,218 @@
+// generates many projects in one go, from a manifest listing several configs or from a
+// directory of config files. capabilities are fetched once and shared by every project
+use crate::api;
+use crate::config::migrate;
+use crate::types::config::{ConfigMetadata, FileType};
+use crate::types::generic::SprintInitConfig;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+pub struct BatchItem {
+    /// where the config came from: manifest entry or file name
+    pub label: String,
+    pub config: SprintInitConfig,
+}
+
+pub struct BatchResult {
+    pub label: String,
+    pub artifact_id: String,
+    pub elapsed: Duration,
+    pub result: Result<PathBuf, String>,
+}
+
+/// Read a manifest:
+///
+/// ```yaml
+/// defaults:            # optional, merged under every entry
+///   groupId: com.acme
+/// projects:
+///   - artifactId: orders
+///   - artifactId: billing
+/// ```
+pub fn parse_manifest(content: &str, file_type: &FileType) -> anyhow::Result<Vec<BatchItem>> {
+    let Value::Object(mut manifest) = migrate::parse_value(content, file_type)? else {
+        anyhow::bail!("Manifest must be a table with a `projects` list");
+    };
+    let defaults = match manifest.remove("defaults") {
+        Some(Value::Object(defaults)) => defaults,
+        None => Map::new(),
+        Some(_) => anyhow::bail!("Manifest `defaults` must be a table of settings"),
+    };
+    let Some(Value::Array(projects)) = manifest.remove("projects") else {
+        anyhow::bail!("Manifest has no `projects` list");
+    };
+
+    projects
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let Value::Object(mut entry) = entry else {
+                anyhow::bail!("projects[{}] must be a table of settings", i);
+            };
+            for (key, value) in &defaults {
+                entry.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+            let config = migrate::migrate(Value::Object(entry))
+                .map_err(|e| anyhow::anyhow!("projects[{}]: {}", i, e))?
+                .config;
+            Ok(BatchItem { label: format!("projects[{}]", i), config })
+        })
+        .collect()
+}
+
+/// Every yaml / json / toml config directly inside `dir`, by file name
+fn read_directory(dir: &Path) -> anyhow::Result<Vec<BatchItem>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            !name.starts_with('.') && ConfigMetadata::from_path(name.to_string()).is_ok()
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let label = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let config = migrate::load(&path.to_string_lossy(), None)
+                .map_err(|e| anyhow::anyhow!("{}: {}", label, e))?
+                .config;
+            Ok(BatchItem { label, config })
+        })
+        .collect()
+}
+
+/// Projects listed by the manifest or directory at `path`
+pub fn load(path: &Path) -> anyhow::Result<Vec<BatchItem>> {
+    let items = if path.is_dir() {
+        read_directory(path)?
+    } else {
+        let content = fs::read_to_string(path)?;
+        let metadata = ConfigMetadata::detect(&path.to_string_lossy(), &content, None);
+        parse_manifest(&content, &metadata.file_type)?
+    };
+
+    if items.is_empty() {
+        anyhow::bail!("No projects found in {}", path.display());
+    }
+    // every project gets its own directory named after the artifact
+    for (i, item) in items.iter().enumerate() {
+        if let Some(other) = items[..i].iter().find(|o| o.config.artifact_id == item.config.artifact_id) {
+            anyhow::bail!(
+                "{} and {} both use artifactId '{}'",
+                other.label,
+                item.label,
+                item.config.artifact_id
+            );
+        }
+    }
+    Ok(items)
+}
+
+/// Generate every item into `dir/<artifactId>`, at most `jobs` at a time.
+/// Results come back in the order of `items`.
+pub async fn generate_all(items: Vec<BatchItem>, extract: bool, dir: &Path, jobs: usize) -> anyhow::Result<Vec<BatchResult>> {
+    let capabilities = Arc::new(api::get_capabilities().await.map_err(|e| anyhow::anyhow!(e.to_string()))?);
+    let permits = Arc::new(Semaphore::new(jobs.max(1)));
+
+    let mut handles = Vec::new();
+    for item in items {
+        let capabilities = capabilities.clone();
+        let permits = permits.clone();
+        let target = dir.join(&item.config.artifact_id);
+        handles.push(tokio::spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let started = Instant::now();
+            let result = super::generate_project_with(&capabilities, &item.config, extract, &target, None, None)
+                .await
+                .map(|_| target)
+                .map_err(|e| e.to_string());
+            BatchResult {
+                label: item.label,
+                artifact_id: item.config.artifact_id,
+                elapsed: started.elapsed(),
+                result,
+            }
+        }));
+    }
+
+    let mut results = Vec::new();
+    for handle in handles {
+        results.push(handle.await?);
+    }
+    Ok(results)
+}
+
+/// Summary table, one row per project
+pub fn summary(results: &[BatchResult]) -> String {
+    let mut table = format!("{:<24} {:<24} {:<8} {:>7}  {}\n", "SOURCE", "ARTIFACT", "STATUS", "TIME", "DETAIL");
+    for r in results {
+        let (status, detail) = match &r.result {
+            Ok(path) => ("ok", path.display().to_string()),
+            Err(e) => ("failed", e.clone()),
+        };
+        table.push_str(&format!(
+            "{:<24} {:<24} {:<8} {:>6.1}s  {}\n",
+            r.label,
+            r.artifact_id,
+            status,
+            r.elapsed.as_secs_f64(),
+            detail
+        ));
+    }
+    let failed = results.iter().filter(|r| r.result.is_err()).count();
+    table.push_str(&format!("\n{} generated, {} failed\n", results.len() - failed, failed));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_entries_inherit_defaults() {
+        let manifest = r#"
+defaults:
+  projectType: gradle-project
+  language: java
+  packaging: jar
+  configurationFileFormat: properties
+  javaVersion: 21
+  groupId: com.acme
+  name: service
+  description: ""
+  packageName: com.acme
+  dependencies: web
+  bootVersion: 3.5.0
+  version: 0.0.1-SNAPSHOT
+projects:
+  - artifactId: orders
+  - artifactId: billing
+    dependencies: web,data-jpa
+"#;
+
+        let items = parse_manifest(manifest, &FileType::Yaml).unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].config.group_id, "com.acme");
+        assert_eq!(items[0].config.dependencies, "web");
+        assert_eq!(items[1].config.dependencies, "web,data-jpa");
+        assert_eq!(items[1].label, "projects[1]");
+    }
+
+    #[test]
+    fn incomplete_entries_are_reported_by_index() {
+        let err = parse_manifest(r#"{ "projects": [ { "artifactId": "orders" } ] }"#, &FileType::Json)
+            .err()
+            .unwrap();
+
+        assert!(err.to_string().starts_with("projects[0]:"), "{}", err);
+    }
+}
@@ -0,0 +1,279 @@
+// renders a SprintInitConfig as a one-off shell command hitting Initializr directly,
+// and reads `spring init ...` command lines back into a config
+use crate::types::generic::SprintInitConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommandStyle {
+    Curl,
+    Httpie,
+    SpringInit,
+}
+
+const DEFAULT_TARGET: &str = "https://start.spring.io";
+
+/// Quote `value` for a POSIX shell when it contains anything beyond plain word characters
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_.,:/=@%+".contains(c));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Request parameters in the order the Initializr link template lists them
+fn request_params(config: &SprintInitConfig) -> Vec<(&'static str, String)> {
+    vec![
+        ("type", config.project_type.clone()),
+        ("dependencies", config.dependencies.clone()),
+        ("packaging", config.packaging.clone()),
+        ("javaVersion", config.java_version.to_string()),
+        ("language", config.language.clone()),
+        ("bootVersion", config.boot_version.clone()),
+        ("groupId", config.group_id.clone()),
+        ("artifactId", config.artifact_id.clone()),
+        ("version", config.version.clone()),
+        ("name", config.name.clone()),
+        ("description", config.description.clone()),
+        ("packageName", config.package_name.clone()),
+        ("configurationFileFormat", config.configuration_file_format.clone()),
+    ]
+}
+
+/// Command line equivalent to generating `config` against the Initializr at `base_url`.
+/// `spring init` has no option for the configuration file format, so that setting is left out.
+pub fn render_command(config: &SprintInitConfig, style: CommandStyle, base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+    let archive = format!("{}.zip", config.artifact_id);
+    let params = request_params(config).into_iter().filter(|(_, v)| !v.is_empty());
+
+    let (mut command, parts) = match style {
+        CommandStyle::Curl => {
+            let mut parts = Vec::new();
+            for (k, v) in params {
+                parts.push(format!("-d {}", shell_quote(&format!("{}={}", k, v))));
+            }
+            parts.push(format!("-o {}", shell_quote(&archive)));
+            (format!("curl {}/starter.zip", base_url), parts)
+        }
+        CommandStyle::Httpie => {
+            let mut parts = Vec::new();
+            for (k, v) in params {
+                parts.push(shell_quote(&format!("{}=={}", k, v)));
+            }
+            parts.push(format!("--output {}", shell_quote(&archive)));
+            (format!("http --download {}/starter.zip", base_url), parts)
+        }
+        CommandStyle::SpringInit => {
+            let mut parts = Vec::new();
+            if base_url != DEFAULT_TARGET {
+                parts.push(format!("--target={}", shell_quote(base_url)));
+            }
+            for (k, v) in params {
+                let option = match k {
+                    "type" => "type",
+                    "dependencies" => "dependencies",
+                    "packaging" => "packaging",
+                    "javaVersion" => "java-version",
+                    "language" => "language",
+                    "bootVersion" => "boot-version",
+                    "groupId" => "group-id",
+                    "artifactId" => "artifact-id",
+                    "version" => "version",
+                    "name" => "name",
+                    "description" => "description",
+                    "packageName" => "package-name",
+                    _ => continue,
+                };
+                parts.push(format!("--{}={}", option, shell_quote(&v)));
+            }
+            parts.push(shell_quote(&archive));
+            ("spring init".to_string(), parts)
+        }
+    };
+
+    // one option per line keeps long commands readable in scripts
+    for part in parts {
+        command.push_str(" \\\n  ");
+        command.push_str(&part);
+    }
+    command
+}
+
+/// Split a command line into words, honouring single quotes, double quotes,
+/// backslash escapes and line continuations
+fn shell_words(line: &str) -> anyhow::Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => anyhow::bail!("Unterminated single quote"),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => current.push(c),
+                            None => anyhow::bail!("Unterminated double quote"),
+                        },
+                        Some(c) => current.push(c),
+                        None => anyhow::bail!("Unterminated double quote"),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') | None => {}
+                Some(c) => {
+                    in_word = true;
+                    current.push(c);
+                }
+            },
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+pub fn is_spring_init(value: &str) -> bool {
+    let mut words = value.split_whitespace();
+    words.next() == Some("spring") && words.next() == Some("init")
+}
+
+/// Parse a `spring init ...` command line into a config; unset options keep their defaults
+pub fn parse_spring_init(line: &str) -> anyhow::Result<SprintInitConfig> {
+    let words = shell_words(line)?;
+    if words.len() < 2 || words[0] != "spring" || words[1] != "init" {
+        anyhow::bail!("Not a spring init command: {}", line);
+    }
+
+    let mut config = SprintInitConfig::default();
+    let mut build: Option<String> = None;
+    let mut has_type = false;
+    let mut args = words.into_iter().skip(2);
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            // target location (directory or archive name)
+            continue;
+        }
+        if matches!(arg.as_str(), "-x" | "--extract" | "-f" | "--force") {
+            continue;
+        }
+
+        let (option, value) = match arg.split_once('=') {
+            Some((o, v)) => (o.to_string(), v.to_string()),
+            None => {
+                let value = args.next().ok_or_else(|| anyhow::anyhow!("Missing value for {}", arg))?;
+                (arg, value)
+            }
+        };
+
+        match option.as_str() {
+            "-t" | "--type" => {
+                has_type = true;
+                config.project_type = value;
+            }
+            "--build" => build = Some(value),
+            "-d" | "--dependencies" => config.dependencies = value,
+            "-p" | "--packaging" => config.packaging = value,
+            "-j" | "--java-version" => {
+                config.java_version = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid java version: {}", value))?
+            }
+            "-l" | "--language" => config.language = value,
+            "-b" | "--boot-version" => config.boot_version = value,
+            "-g" | "--group-id" => config.group_id = value,
+            "-a" | "--artifact-id" => config.artifact_id = value,
+            "-v" | "--version" => config.version = value,
+            "-n" | "--name" => config.name = value,
+            "--description" => config.description = value,
+            "--package-name" => config.package_name = value,
+            "--target" | "--format" => {}
+            _ => anyhow::bail!("Unsupported spring init option: {}", option),
+        }
+    }
+
+    if !has_type && let Some(build) = build {
+        config.project_type = format!("{}-project", build);
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SprintInitConfig {
+        SprintInitConfig {
+            project_type: "gradle-project".to_string(),
+            description: "Rake's garden service".to_string(),
+            dependencies: "web,lombok".to_string(),
+            java_version: 21,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renders_curl_with_quoted_values() {
+        let command = render_command(&config(), CommandStyle::Curl, "https://start.spring.io/");
+
+        assert!(command.starts_with("curl https://start.spring.io/starter.zip \\\n"));
+        assert!(command.contains("-d type=gradle-project"));
+        assert!(command.contains(r"-d 'description=Rake'\''s garden service'"));
+        assert!(command.ends_with("-o demo.zip"));
+    }
+
+    #[test]
+    fn renders_httpie_query_params() {
+        let command = render_command(&config(), CommandStyle::Httpie, "https://start.spring.io/");
+
+        assert!(command.starts_with("http --download https://start.spring.io/starter.zip \\\n"));
+        assert!(command.contains("dependencies==web,lombok"));
+    }
+
+    #[test]
+    fn spring_init_round_trips() {
+        let command = render_command(&config(), CommandStyle::SpringInit, "https://start.spring.io/");
+
+        assert!(!command.contains("--target"));
+        assert_eq!(parse_spring_init(&command).unwrap(), config());
+    }
+
+    #[test]
+    fn parses_short_options_and_build() {
+        let parsed = parse_spring_init("spring init --build gradle -d web,jpa -j 17 -a rake rake.zip").unwrap();
+
+        assert_eq!(parsed.project_type, "gradle-project");
+        assert_eq!(parsed.dependencies, "web,jpa");
+        assert_eq!(parsed.java_version, 17);
+        assert_eq!(parsed.artifact_id, "rake");
+    }
+}
@@ -0,0 +1,132 @@
+// optional git step after extraction: init with a branch name, commit everything the generated
+// .gitignore lets through and register a remote, all with the local git binary
+use super::overlay;
+use crate::types::config::GlobalConfig;
+use crate::types::generic::SprintInitConfig;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+pub const DEFAULT_BRANCH: &str = "main";
+pub const DEFAULT_COMMIT_MESSAGE: &str = "Generate {{artifactId}} with Spring Boot {{bootVersion}}";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitOptions {
+    pub branch: String,
+    /// may use the overlay placeholders, e.g. {{artifactId}}
+    pub commit_message: String,
+    /// may use the overlay placeholders as well
+    pub remote: Option<String>,
+}
+
+impl Default for GitOptions {
+    fn default() -> Self {
+        GitOptions { branch: DEFAULT_BRANCH.to_string(), commit_message: DEFAULT_COMMIT_MESSAGE.to_string(), remote: None }
+    }
+}
+
+impl GitOptions {
+    /// Options from the gitBranch, gitCommitMessage and gitRemote settings
+    pub fn from_settings(settings: &GlobalConfig) -> Self {
+        let defaults = GitOptions::default();
+        GitOptions {
+            branch: settings.git_branch.clone().unwrap_or(defaults.branch),
+            commit_message: settings.git_commit_message.clone().unwrap_or(defaults.commit_message),
+            remote: settings.git_remote.clone(),
+        }
+    }
+
+    /// Options when gitInit is set
+    pub fn enabled(settings: &GlobalConfig) -> Option<Self> {
+        settings.git_init.unwrap_or(false).then(|| Self::from_settings(settings))
+    }
+}
+
+/// Run git in `project` with `env` added to its environment
+fn git(project: &Path, env: &[(&str, &str)], args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .envs(env.iter().copied())
+        .current_dir(project)
+        .output()
+        .map_err(|e| anyhow::anyhow!("Could not run git: {}", e))?;
+    if !output.status.success() {
+        anyhow::bail!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Turn the extracted project in `project` into a repository with one commit.
+/// `archive` is the downloaded zip next to the project, kept out of the repository.
+/// Returns a one line summary.
+pub fn init(project: &Path, config: &SprintInitConfig, archive: &str, options: &GitOptions) -> anyhow::Result<String> {
+    init_with_env(project, config, archive, options, &[])
+}
+
+fn init_with_env(
+    project: &Path,
+    config: &SprintInitConfig,
+    archive: &str,
+    options: &GitOptions,
+    env: &[(&str, &str)],
+) -> anyhow::Result<String> {
+    if project.join(".git").exists() {
+        return Ok(format!("{} is already a git repository, left as it is", project.display()));
+    }
+    let variables = overlay::variables(config);
+
+    git(project, env, &["init", "--quiet"])?;
+    // works with every git version, unlike `init -b`
+    git(project, env, &["symbolic-ref", "HEAD", &format!("refs/heads/{}", options.branch)])?;
+    let exclude = project.join(".git/info/exclude");
+    let mut excluded = fs::read_to_string(&exclude).unwrap_or_default();
+    excluded.push_str(&format!("/{}\n", archive));
+    fs::create_dir_all(project.join(".git/info"))?;
+    fs::write(&exclude, excluded)?;
+
+    git(project, env, &["add", "--all"])?;
+    git(project, env, &["commit", "--quiet", "-m", &overlay::render(&options.commit_message, &variables)])?;
+    let mut summary = format!("git repository initialised on {}", options.branch);
+    if let Some(remote) = &options.remote {
+        let remote = overlay::render(remote, &variables);
+        git(project, env, &["remote", "add", "origin", &remote])?;
+        summary.push_str(&format!(", origin {}", remote));
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commits_the_project_without_ignored_files_or_the_archive() {
+        let project = std::env::temp_dir().join(format!("spring-tui-git-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&project);
+        fs::create_dir_all(project.join("target")).unwrap();
+        fs::write(project.join(".gitignore"), "target/\n").unwrap();
+        fs::write(project.join("pom.xml"), "<project/>\n").unwrap();
+        fs::write(project.join("target/app.jar"), "jar").unwrap();
+        fs::write(project.join("demo.zip"), "zip").unwrap();
+        let options = GitOptions {
+            branch: "trunk".to_string(),
+            commit_message: "Start {{artifactId}}".to_string(),
+            remote: Some("git@example.com:acme/{{artifactId}}.git".to_string()),
+        };
+        // keep the test independent of the machine's git identity
+        let identity = [
+            ("GIT_AUTHOR_NAME", "spring-tui"),
+            ("GIT_AUTHOR_EMAIL", "spring-tui@example.com"),
+            ("GIT_COMMITTER_NAME", "spring-tui"),
+            ("GIT_COMMITTER_EMAIL", "spring-tui@example.com"),
+        ];
+
+        let summary = init_with_env(&project, &SprintInitConfig::default(), "demo.zip", &options, &identity).unwrap();
+
+        assert_eq!(summary, "git repository initialised on trunk, origin git@example.com:acme/demo.git");
+        assert_eq!(git(&project, &[], &["log", "--format=%s"]).unwrap(), "Start demo");
+        assert_eq!(git(&project, &[], &["rev-parse", "--abbrev-ref", "HEAD"]).unwrap(), "trunk");
+        let files = git(&project, &[], &["ls-files"]).unwrap();
+        assert!(files.contains("pom.xml") && !files.contains("app.jar") && !files.contains("demo.zip"));
+    }
+}
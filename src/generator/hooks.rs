@@ -0,0 +1,186 @@
+// hooks: user commands run at fixed stages of a generation, configured with the
+// beforeRequestHook, afterDownloadHook and afterExtractHook keys. Each command runs through the
+// shell in the output directory and reads one JSON document from stdin:
+//
+//   {
+//     "protocolVersion": 1,
+//     "stage": "before-request" | "after-download" | "after-extract",
+//     "config": { ...the project config, as in exported configs... },
+//     "requestUrl": "https://start.spring.io/starter.zip?...",
+//     "outputDir": "/abs/output/dir",
+//     "archive": "/abs/output/dir/demo.zip",      // after-download and after-extract
+//     "projectDir": "/abs/output/dir",            // after-extract only
+//     "dependencies": [{ "id", "groupId", "artifactId", "version"?, "scope", "bom"?, "repository"? }]
+//   }
+//
+// A non-zero exit aborts the generation. Whatever the hook prints is shown to the user.
+// Hooks are passed to the generations a user asked for (-f, the tui, add-module); batch items
+// and the throwaway skeletons of `upgrade` run without them.
+use super::lock::LockedDependency;
+use crate::types::config::GlobalConfig;
+use crate::types::generic::SprintInitConfig;
+use serde::Serialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+pub const PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Stage {
+    BeforeRequest,
+    AfterDownload,
+    AfterExtract,
+}
+
+impl Stage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stage::BeforeRequest => "before-request",
+            Stage::AfterDownload => "after-download",
+            Stage::AfterExtract => "after-extract",
+        }
+    }
+}
+
+/// Hook command per stage
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HookCommands {
+    pub before_request: Option<String>,
+    pub after_download: Option<String>,
+    pub after_extract: Option<String>,
+}
+
+impl HookCommands {
+    /// The hooks configured in `settings`, None when there are none
+    pub fn from_settings(settings: &GlobalConfig) -> Option<Self> {
+        let hooks = HookCommands {
+            before_request: settings.before_request_hook.clone(),
+            after_download: settings.after_download_hook.clone(),
+            after_extract: settings.after_extract_hook.clone(),
+        };
+        (hooks != HookCommands::default()).then_some(hooks)
+    }
+
+    fn get(&self, stage: Stage) -> Option<&str> {
+        match stage {
+            Stage::BeforeRequest => self.before_request.as_deref(),
+            Stage::AfterDownload => self.after_download.as_deref(),
+            Stage::AfterExtract => self.after_extract.as_deref(),
+        }
+    }
+}
+
+/// Document sent to the hooks, completed as the generation moves on
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HookEvent {
+    pub protocol_version: u32,
+    pub stage: Stage,
+    pub config: SprintInitConfig,
+    pub request_url: String,
+    pub output_dir: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_dir: Option<PathBuf>,
+    pub dependencies: Vec<LockedDependency>,
+}
+
+impl HookEvent {
+    pub fn new(config: &SprintInitConfig, request_url: &str, dir: &Path, dependencies: Vec<LockedDependency>) -> Self {
+        HookEvent {
+            protocol_version: PROTOCOL_VERSION,
+            stage: Stage::BeforeRequest,
+            config: config.clone(),
+            request_url: request_url.to_string(),
+            output_dir: std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf()),
+            archive: None,
+            project_dir: None,
+            dependencies,
+        }
+    }
+}
+
+/// What a hook printed, stdout and stderr interleaved by stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct HookRun {
+    pub stage: Stage,
+    pub output: String,
+}
+
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+/// Run `command` for `event`, failing when it exits non-zero
+pub fn run_command(command: &str, event: &HookEvent) -> anyhow::Result<HookRun> {
+    let mut child = shell(command)
+        .current_dir(&event.output_dir)
+        .env("SPRING_TUI_HOOK_STAGE", event.stage.name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Could not start the {} hook `{}`: {}", event.stage.name(), command, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // a hook that doesn't read its input closes the pipe early, that's fine
+        let _ = stdin.write_all(serde_json::to_string_pretty(event)?.as_bytes());
+    }
+    let result = child.wait_with_output()?;
+
+    let mut output = String::from_utf8_lossy(&result.stdout).to_string();
+    output.push_str(&String::from_utf8_lossy(&result.stderr));
+    let output = output.trim_end().to_string();
+    if !result.status.success() {
+        let code = result.status.code().map(|c| c.to_string()).unwrap_or_else(|| "signal".to_string());
+        anyhow::bail!("{} hook failed (exit {}), generation aborted\n{}", event.stage.name(), code, output);
+    }
+    Ok(HookRun { stage: event.stage, output })
+}
+
+/// Run the hook of `hooks` for `stage`, if any, collecting its output in `runs`
+pub fn run(hooks: Option<&HookCommands>, stage: Stage, event: &mut HookEvent, runs: &mut Vec<HookRun>) -> anyhow::Result<()> {
+    event.stage = stage;
+    let Some(command) = hooks.and_then(|hooks| hooks.get(stage)) else {
+        return Ok(());
+    };
+    runs.push(run_command(command, event)?);
+    Ok(())
+}
+
+/// Hook output for display, one block per hook that printed anything
+pub fn format_runs(runs: &[HookRun]) -> String {
+    runs.iter()
+        .filter(|r| !r.output.is_empty())
+        .map(|r| format!("[{} hook]\n{}", r.stage.name(), r.output))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hooks_read_the_event_and_abort_on_failure() {
+        let dir = std::env::temp_dir();
+        let mut event = HookEvent::new(&SprintInitConfig::default(), "https://start.spring.io/starter.zip?type=maven-project", &dir, Vec::new());
+        event.stage = Stage::AfterDownload;
+
+        let run = run_command("grep -o '\"stage\": \"[a-z-]*\"'; echo \"$SPRING_TUI_HOOK_STAGE\"", &event).unwrap();
+        assert_eq!(run.output, "\"stage\": \"after-download\"\nafter-download");
+
+        let err = run_command("echo missing license header >&2; exit 3", &event).unwrap_err().to_string();
+        assert!(err.contains("exit 3") && err.contains("missing license header"));
+    }
+}
@@ -0,0 +1,269 @@
+// lockfile recorded with every generation, spring-tui.lock in an extracted project and
+// <artifactId>.lock next to an archive, so it can be traced back to the Initializr instance, Boot
+// version and dependency coordinates that produced it. The coordinates are best effort: when
+// /dependencies can't be reached the lock records none, and `regenerate` skips the drift check.
+use crate::api;
+use crate::types::api::InitializrDependencies;
+use crate::types::generic::SprintInitConfig;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const LOCK_FILE: &str = "spring-tui.lock";
+const LOCK_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockFile {
+    pub lock_version: u32,
+    /// Initializr instance the project came from
+    pub base_url: String,
+    /// exact starter.zip request, replayed by `spring-tui regenerate`
+    pub request_url: String,
+    /// boot version reported by /dependencies for this request
+    pub boot_version: String,
+    /// sha256 of the archive as Initializr served it, before the provenance comment
+    pub archive_sha256: String,
+    pub config: SprintInitConfig,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<LockedDependency>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedDependency {
+    pub id: String,
+    pub group_id: String,
+    pub artifact_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bom: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+}
+
+/// Where the lock of a generation into `dir` goes
+pub fn lock_path(dir: &Path, config: &SprintInitConfig, extracted: bool) -> PathBuf {
+    if extracted {
+        dir.join(LOCK_FILE)
+    } else {
+        dir.join(format!("{}.lock", config.artifact_id))
+    }
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Coordinates of the dependencies selected in `config`, in selection order
+pub fn resolve(config: &SprintInitConfig, dependencies: &InitializrDependencies) -> Vec<LockedDependency> {
+    config
+        .dependencies
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .filter_map(|id| {
+            dependencies.dependencies.get(id).map(|d| LockedDependency {
+                id: id.to_string(),
+                group_id: d.group_id.clone(),
+                artifact_id: d.artifact_id.clone(),
+                version: d.version.clone(),
+                scope: d.scope.clone(),
+                bom: d.bom.clone(),
+                repository: d.repository.clone(),
+            })
+        })
+        .collect()
+}
+
+impl LockFile {
+    /// Lock for `archive`, downloaded from `request_url` for `config`; without `dependencies`
+    /// the Boot version is the requested one and no coordinates are recorded
+    pub fn new(config: &SprintInitConfig, request_url: &str, archive: &[u8], dependencies: Option<&InitializrDependencies>) -> Self {
+        LockFile {
+            lock_version: LOCK_VERSION,
+            base_url: api::util::get_base_url(),
+            request_url: request_url.to_string(),
+            boot_version: dependencies.map_or_else(|| config.boot_version.clone(), |d| d.boot_version.clone()),
+            archive_sha256: sha256_hex(archive),
+            config: config.clone(),
+            dependencies: dependencies.map(|d| resolve(config, d)).unwrap_or_default(),
+        }
+    }
+
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let lock: LockFile = toml::from_str(&content)?;
+        if lock.lock_version > LOCK_VERSION {
+            anyhow::bail!("{} was written by a newer spring-tui, please upgrade", path.display());
+        }
+        Ok(lock)
+    }
+
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Outcome of replaying a lock
+pub struct Regenerated {
+    pub archive: PathBuf,
+    pub checksum_matches: bool,
+    /// dependencies whose coordinates no longer match the lock
+    pub drift: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+/// Replay the request recorded in `lock` into `dir`, comparing the result with the lock.
+/// Recipes and overlays are the ones installed now, not the ones of the original generation.
+pub async fn regenerate(lock: &LockFile, dir: &Path, extract: bool) -> anyhow::Result<Regenerated> {
+    let bytes = api::transport::get(&lock.request_url, None)
+        .await
+        .and_then(|response| response.into_success())
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let mut warnings = Vec::new();
+    let drift = match api::get_dependencies_for(&lock.config.boot_version).await {
+        Ok(current) => drift(lock, &current),
+        Err(e) => {
+            warnings.push(format!(
+                "No dependency coordinates for Spring Boot {}, drift not checked: {}",
+                lock.config.boot_version, e
+            ));
+            Vec::new()
+        }
+    };
+
+    fs::create_dir_all(dir)?;
+    let archive = dir.join(format!("{}.zip", lock.config.artifact_id));
+    if super::provenance::enabled() {
+        fs::write(&archive, super::provenance::with_comment(bytes.to_vec(), &lock.config)?)?;
+    } else {
+        fs::write(&archive, &bytes)?;
+    }
+    if extract {
+        zip::ZipArchive::new(fs::File::open(&archive)?)?.extract(dir)?;
+        super::finish_extraction(dir, &lock.config)?;
+    }
+    lock.write(&lock_path(dir, &lock.config, extract))?;
+
+    Ok(Regenerated { archive, checksum_matches: sha256_hex(&bytes) == lock.archive_sha256, drift, warnings })
+}
+
+/// What changed between the lock and the coordinates `current` resolves to, one line per change
+pub fn drift(lock: &LockFile, current: &InitializrDependencies) -> Vec<String> {
+    let mut drift = Vec::new();
+    if current.boot_version != lock.boot_version {
+        drift.push(format!("bootVersion {} -> {}", lock.boot_version, current.boot_version));
+    }
+    let now = resolve(&lock.config, current);
+    for locked in &lock.dependencies {
+        match now.iter().find(|d| d.id == locked.id) {
+            Some(now) if now != locked => drift.push(format!(
+                "{}: {}:{}:{} -> {}:{}:{}",
+                locked.id,
+                locked.group_id,
+                locked.artifact_id,
+                locked.version.as_deref().unwrap_or("managed"),
+                now.group_id,
+                now.artifact_id,
+                now.version.as_deref().unwrap_or("managed")
+            )),
+            Some(_) => {}
+            None => drift.push(format!("{}: no longer offered", locked.id)),
+        }
+    }
+    drift
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::api::Dependency;
+    use std::collections::HashMap;
+
+    #[test]
+    fn lock_round_trips_through_toml() {
+        let config = SprintInitConfig { dependencies: "web,unknown".to_string(), ..Default::default() };
+        let dependencies = InitializrDependencies {
+            boot_version: "3.5.0".to_string(),
+            dependencies: HashMap::from([(
+                "web".to_string(),
+                Dependency {
+                    group_id: "org.springframework.boot".to_string(),
+                    artifact_id: "spring-boot-starter-web".to_string(),
+                    scope: "compile".to_string(),
+                    bom: None,
+                    version: None,
+                    repository: None,
+                },
+            )]),
+            boms: HashMap::new(),
+            repositories: HashMap::new(),
+        };
+        let lock = LockFile {
+            lock_version: LOCK_VERSION,
+            base_url: "https://start.spring.io/".to_string(),
+            request_url: "https://start.spring.io/starter.zip?type=maven-project&dependencies=web".to_string(),
+            boot_version: dependencies.boot_version.clone(),
+            archive_sha256: sha256_hex(b"archive"),
+            dependencies: resolve(&config, &dependencies),
+            config,
+        };
+
+        let toml = toml::to_string_pretty(&lock).unwrap();
+
+        assert_eq!(lock.dependencies.len(), 1);
+        assert_eq!(toml::from_str::<LockFile>(&toml).unwrap(), lock);
+        assert_eq!(lock.archive_sha256.len(), 64);
+    }
+
+    fn coordinates(boot_version: &str, entries: &[(&str, &str, Option<&str>)]) -> InitializrDependencies {
+        InitializrDependencies {
+            boot_version: boot_version.to_string(),
+            dependencies: entries
+                .iter()
+                .map(|(id, artifact_id, version)| {
+                    let group_id = if *id == "h2" { "com.h2database" } else { "org.springframework.boot" };
+                    let dependency = Dependency {
+                        group_id: group_id.to_string(),
+                        artifact_id: artifact_id.to_string(),
+                        scope: "compile".to_string(),
+                        bom: None,
+                        version: version.map(str::to_string),
+                        repository: None,
+                    };
+                    (id.to_string(), dependency)
+                })
+                .collect(),
+            boms: HashMap::new(),
+            repositories: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn drift_names_every_change_since_the_lock() {
+        let config = SprintInitConfig { dependencies: "web,h2".to_string(), boot_version: "3.5.0".to_string(), ..Default::default() };
+        let then = coordinates("3.5.0", &[("web", "spring-boot-starter-web", None), ("h2", "h2", Some("2.2.224"))]);
+        let lock = LockFile::new(&config, "https://start.spring.io/starter.zip?type=maven-project", b"archive", Some(&then));
+        let now = coordinates("3.5.3", &[("web", "spring-boot-starter-webmvc", None)]);
+
+        assert!(drift(&lock, &then).is_empty());
+        assert_eq!(
+            drift(&lock, &now),
+            vec![
+                "bootVersion 3.5.0 -> 3.5.3",
+                "web: org.springframework.boot:spring-boot-starter-web:managed -> org.springframework.boot:spring-boot-starter-webmvc:managed",
+                "h2: no longer offered",
+            ]
+        );
+
+        let unresolved = LockFile::new(&config, &lock.request_url, b"archive", None);
+        assert_eq!(unresolved.boot_version, "3.5.0");
+        assert!(unresolved.dependencies.is_empty());
+        assert_eq!(drift(&unresolved, &then), Vec::<String>::new());
+    }
+}
@@ -35,8 +35,30 @@
 */
 
 
+pub mod batch;
+pub mod command;
+pub mod git;
+pub mod hooks;
+pub mod lock;
+pub mod offline;
+pub mod overlay;
+pub mod provenance;
+pub mod properties;
+pub mod recipe;
+pub mod share;
+pub mod workspace;
+
+use crate::api::snapshot::Backend;
+use crate::project::BuildKind;
+use crate::types::api::InitializrCapabilities;
 use crate::types::generic::SprintInitConfig;
 use crate::{api, types};
+use std::path::Path;
+
+/// `text` escaped for an xml element, shared by the poms the generator writes
+fn xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
 
 pub fn download_url(config: &SprintInitConfig, base_url: &str) -> String {
     let mut url = base_url.to_string();
@@ -58,51 +80,208 @@ pub fn download_url(config: &SprintInitConfig, base_url: &str) -> String {
     url
 }
 
-pub async fn generate_project(config: &SprintInitConfig, extract_project: bool) -> Result<(), Box<dyn std::error::Error>> {
+/// What happened besides the download, for the user
+#[derive(Debug, Default)]
+pub struct Generation {
+    pub hook_runs: Vec<hooks::HookRun>,
+    /// summary of the git step, when it ran
+    pub git: Option<String>,
+    /// built by the offline backend rather than downloaded
+    pub offline: bool,
+    /// steps that were skipped without failing the generation
+    pub warnings: Vec<String>,
+}
+
+impl Generation {
+    /// Hook output and git summary, empty when there is nothing to tell
+    pub fn report(&self) -> String {
+        let mut lines: Vec<String> = self.warnings.iter().map(|w| format!("! {}", w)).collect();
+        if self.offline {
+            lines.push(format!("Generated offline from the snapshot in {}", api::snapshot::snapshot_dir().display()));
+        }
+        let hooks = hooks::format_runs(&self.hook_runs);
+        if !hooks.is_empty() {
+            lines.push(hooks);
+        }
+        lines.extend(self.git.clone());
+        lines.join("\n")
+    }
+}
+
+/// Download (and optionally extract) the project into the working directory
+pub async fn generate_project(config: &SprintInitConfig, extract_project: bool) -> Result<Generation, Box<dyn std::error::Error>> {
+    generate_project_into(config, extract_project, Path::new("."), None, None).await
+}
+
+/// Download (and optionally extract) the project into `dir`, creating it if needed.
+/// `git` turns the extracted project into a repository, `hooks` run at each stage.
+pub async fn generate_project_into(
+    config: &SprintInitConfig,
+    extract_project: bool,
+    dir: &Path,
+    git: Option<&git::GitOptions>,
+    hooks: Option<&hooks::HookCommands>,
+) -> Result<Generation, Box<dyn std::error::Error>> {
     // Get capabilities from API
     let capabilities = api::get_capabilities().await?;
-    
+    generate_project_with(&capabilities, config, extract_project, dir, git, hooks).await
+}
+
+/// Like generate_project_into, with capabilities fetched by the caller so they can be shared
+/// An empty boot version in `config` takes the default the capabilities advertise.
+pub async fn generate_project_with(
+    capabilities: &InitializrCapabilities,
+    config: &SprintInitConfig,
+    extract_project: bool,
+    dir: &Path,
+    git: Option<&git::GitOptions>,
+    hooks: Option<&hooks::HookCommands>,
+) -> Result<Generation, Box<dyn std::error::Error>> {
+    let defaulted;
+    let config = if config.boot_version.is_empty() {
+        let boot_version = SprintInitConfig::from_capabilities(capabilities).boot_version;
+        if boot_version.is_empty() {
+            return Err("No Spring Boot version set and Initializr advertises none".into());
+        }
+        defaulted = SprintInitConfig { boot_version, ..config.clone() };
+        &defaulted
+    } else {
+        config
+    };
+    let download_link = link_url(capabilities, &config.project_type, config)?;
+    std::fs::create_dir_all(dir)?;
+    let mut generation = Generation::default();
+    // coordinates for the lock and the hooks; a lock without them beats a failed generation
+    let mut dependencies = None;
+    match api::get_dependencies_for(&config.boot_version).await {
+        Ok(fetched) => dependencies = Some(fetched),
+        Err(e) => generation.warnings.push(format!(
+            "No dependency coordinates for Spring Boot {}, the lock records none: {}",
+            config.boot_version, e
+        )),
+    }
+    let locked = dependencies.as_ref().map(|d| lock::resolve(config, d)).unwrap_or_default();
+    let mut event = hooks::HookEvent::new(config, &download_link, dir, locked);
+    hooks::run(hooks, hooks::Stage::BeforeRequest, &mut event, &mut generation.hook_runs).map_err(|e| e.to_string())?;
+
+    let downloaded = match api::snapshot::backend() {
+        Backend::Offline => None,
+        Backend::Initializr => Some(fetch(&download_link).await?),
+        Backend::Auto => match fetch(&download_link).await {
+            Ok(bytes) => Some(bytes),
+            Err(e) if api::snapshot::is_unreachable(e.as_ref()) => None,
+            Err(e) => return Err(e),
+        },
+    };
+    let bytes = match downloaded {
+        Some(bytes) => bytes,
+        None => {
+            generation.offline = true;
+            // the offline build cannot do without them
+            if dependencies.is_none() {
+                dependencies = Some(api::get_dependencies_for(&config.boot_version).await?);
+            }
+            offline::archive(config, dependencies.as_ref().ok_or("No dependency coordinates")?).map_err(|e| e.to_string())?
+        }
+    };
+    let lock = lock::LockFile::new(config, &download_link, &bytes, dependencies.as_ref());
+    // the lock keeps the checksum of the archive as Initializr served it
+    let bytes = if provenance::enabled() { provenance::with_comment(bytes, config).map_err(|e| e.to_string())? } else { bytes };
+
+    // Save to file
+    let file_path = dir.join(format!("{}.zip", config.artifact_id));
+    std::fs::write(&file_path, bytes)?;
+    lock.write(&lock::lock_path(dir, config, extract_project)).map_err(|e| e.to_string())?;
+    event.archive = Some(event.output_dir.join(format!("{}.zip", config.artifact_id)));
+    hooks::run(hooks, hooks::Stage::AfterDownload, &mut event, &mut generation.hook_runs).map_err(|e| e.to_string())?;
+
+    // Extract the zip
+    if extract_project {
+        let file = std::fs::File::open(&file_path)?;
+        let mut archive = zip::ZipArchive::new(file)?;
+        archive.extract(dir)?;
+        finish_extraction(dir, config).map_err(|e| e.to_string())?;
+        println!("Project extracted successfully!");
+        event.project_dir = Some(event.output_dir.clone());
+        hooks::run(hooks, hooks::Stage::AfterExtract, &mut event, &mut generation.hook_runs).map_err(|e| e.to_string())?;
+        // last, so the initial commit has what the after-extract hook changed
+        if let Some(options) = git {
+            let archive = format!("{}.zip", config.artifact_id);
+            generation.git = Some(git::init(dir, config, &archive, options).map_err(|e| e.to_string())?);
+        }
+    }
+
+    Ok(generation)
+}
+
+/// What follows the extraction of `config`'s archive into `dir`: application settings, recipes,
+/// overlays and the provenance file. Shared by generation and `regenerate`.
+pub fn finish_extraction(dir: &Path, config: &SprintInitConfig) -> anyhow::Result<()> {
+    properties::apply_settings(dir, config)?;
+    recipe::apply_all(dir, config)?;
+    overlay::apply_all(dir, config)?;
+    if provenance::enabled() {
+        provenance::write(dir, config)?;
+    }
+    Ok(())
+}
+
+/// Request url of the capabilities link `link` for `config`
+fn link_url(
+    capabilities: &InitializrCapabilities,
+    link: &str,
+    config: &SprintInitConfig,
+) -> Result<String, Box<dyn std::error::Error>> {
     // Get the base URL from the capabilities based on project type
     let base_url = capabilities
         .links
-        .get(&config.project_type)
+        .get(link)
         .ok_or("Unsupported project type")?
         .href
         .split('{')
         .next()
         .ok_or("Invalid URL format")?;
 
-    let download_link = download_url(config, base_url);
-    // println!("Download URL: {}", download_link);
-
-    // Download the project
-    let client = reqwest::Client::new();
-    let response = client.get(&download_link).send().await?;
-    
-    if response.status().is_success() {
-        let bytes = response.bytes().await?;
-        
-        // Save to file
-        let file_path = format!("{}.zip", config.artifact_id);
-        std::fs::write(&file_path, bytes)?;
-        // println!("Project downloaded to: {}", file_path);
-        
-        // Extract the zip
-        if extract_project {
-            let file = std::fs::File::open(&file_path)?;
-            let mut archive = zip::ZipArchive::new(file)?;
-            archive.extract(".")?;
-            println!("Project extracted successfully!");
-        }
-        
-        Ok(())
-    } else {
-        let error_text = response.text().await.unwrap_or_default();
-        let error_response: Result<types::generic::ErrorResponse, _> = serde_json::from_str(&error_text);
-        if let Ok(err) = error_response {
-            Err(format!("Download failed: {}",err.message).into())
-        } else {  
-            Err(format!("Download failed: {}", error_text).into())
+    Ok(download_url(config, base_url))
+}
+
+/// Fetch what the capabilities link `link` produces for `config`, with the request url
+async fn download(
+    capabilities: &InitializrCapabilities,
+    link: &str,
+    config: &SprintInitConfig,
+) -> Result<(String, Vec<u8>), Box<dyn std::error::Error>> {
+    let download_link = link_url(capabilities, link, config)?;
+    let bytes = fetch(&download_link).await?;
+    Ok((download_link, bytes))
+}
+
+async fn fetch(download_link: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    api::transport::get(download_link, None).await?.into_success()
+}
+
+/// Build file Initializr generates for `config`, through the maven-build / gradle-build links.
+/// There is no link for the Kotlin DSL, so that one is taken out of the project archive.
+pub async fn fetch_build_file(
+    capabilities: &InitializrCapabilities,
+    config: &SprintInitConfig,
+    kind: BuildKind,
+) -> Result<String, Box<dyn std::error::Error>> {
+    match kind {
+        BuildKind::Maven => Ok(String::from_utf8(download(capabilities, "maven-build", config).await?.1)?),
+        BuildKind::GradleGroovy => Ok(String::from_utf8(download(capabilities, "gradle-build", config).await?.1)?),
+        BuildKind::GradleKotlin => {
+            let (_, bytes) = download(capabilities, "gradle-project-kotlin", config).await?;
+            let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))?;
+            let name = archive
+                .file_names()
+                .filter(|n| n.ends_with(kind.file_name()))
+                .min_by_key(|n| n.len())
+                .ok_or("No build.gradle.kts in the generated project")?
+                .to_string();
+            let mut content = String::new();
+            std::io::Read::read_to_string(&mut archive.by_name(&name)?, &mut content)?;
+            Ok(content)
         }
     }
 }
@@ -128,12 +307,39 @@ pub fn generate_project_config_file(
         format!("config.{}", ext_str)
     };
 
-    let content = match extension {
+    let content = render_config(config, &extension)?;
+
+    std::fs::write(filename, content)?;
+    Ok(())
+}
+
+pub fn render_config(
+    config: &SprintInitConfig,
+    extension: &types::config::FileType,
+) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(match extension {
         types::config::FileType::Yaml => serde_yaml::to_string(config)?,
         types::config::FileType::Json => serde_json::to_string_pretty(config)?,
         types::config::FileType::Toml => toml::to_string_pretty(config)?,
+    })
+}
+
+/// Write the command line for `config` as a shell script, returning its path
+pub fn generate_command_file(
+    config: &SprintInitConfig,
+    style: command::CommandStyle,
+    custom_filename: Option<String>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let filename = match custom_filename {
+        Some(name) if name.ends_with(".sh") => name,
+        Some(name) => format!("{}.sh", name),
+        None => "spring-init.sh".to_string(),
     };
 
-    std::fs::write(filename, content)?;
-    Ok(())
+    let script = format!(
+        "#!/bin/sh\n{}\n",
+        command::render_command(config, style, &api::util::get_base_url())
+    );
+    std::fs::write(&filename, script)?;
+    Ok(filename)
 }
\ No newline at end of file
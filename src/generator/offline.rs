@@ -0,0 +1,461 @@
+// offline backend: a minimal project in the layout start.spring.io produces (build file, wrapper
+// properties, application and test class, application config, .gitignore), built from the config
+// and the dependency coordinates and BOMs of a /dependencies snapshot. The wrapper scripts are
+// not included; the wrapper properties point at the public distributions, edit them for a mirror.
+use super::xml;
+use crate::types::api::InitializrDependencies;
+use crate::types::generic::SprintInitConfig;
+use std::collections::BTreeMap;
+use std::io::{Cursor, Write};
+
+const DEPENDENCY_MANAGEMENT_PLUGIN_VERSION: &str = "1.1.7";
+const KOTLIN_VERSION: &str = "1.9.25";
+const MAVEN_VERSION: &str = "3.9.11";
+const MAVEN_WRAPPER_VERSION: &str = "3.3.2";
+const GRADLE_VERSION: &str = "8.14.3";
+
+/// A dependency as it goes into the build
+struct Coordinates {
+    group_id: String,
+    artifact_id: String,
+    version: Option<String>,
+    scope: String,
+}
+
+impl Coordinates {
+    fn new(group_id: &str, artifact_id: &str, scope: &str) -> Self {
+        Coordinates { group_id: group_id.to_string(), artifact_id: artifact_id.to_string(), version: None, scope: scope.to_string() }
+    }
+
+    fn notation(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}:{}:{}", self.group_id, self.artifact_id, version),
+            None => format!("{}:{}", self.group_id, self.artifact_id),
+        }
+    }
+}
+
+/// What the build needs beyond the config
+struct Build {
+    dependencies: Vec<Coordinates>,
+    /// group:artifact:version
+    boms: Vec<String>,
+    /// (id, name, url, snapshots)
+    repositories: Vec<(String, String, String, bool)>,
+}
+
+fn resolve(config: &SprintInitConfig, snapshot: &InitializrDependencies) -> anyhow::Result<Build> {
+    let mut dependencies = Vec::new();
+    let mut boms = BTreeMap::new();
+    let mut repository_ids = Vec::new();
+    for id in config.dependencies.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        let dependency = snapshot
+            .dependencies
+            .get(id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown dependency '{}' for Spring Boot {}", id, snapshot.boot_version))?;
+        dependencies.push(Coordinates {
+            version: dependency.version.clone(),
+            ..Coordinates::new(&dependency.group_id, &dependency.artifact_id, &dependency.scope)
+        });
+        repository_ids.extend(dependency.repository.clone());
+        if let Some(bom_id) = &dependency.bom {
+            let bom = snapshot.boms.get(bom_id).ok_or_else(|| anyhow::anyhow!("BOM '{}' missing from the snapshot", bom_id))?;
+            boms.insert(bom_id.clone(), format!("{}:{}:{}", bom.group_id, bom.artifact_id, bom.version));
+            repository_ids.extend(bom.repositories.iter().cloned());
+        }
+    }
+
+    // Initializr always brings the base starter, unless another starter already does
+    if !dependencies.iter().any(|d| d.artifact_id.starts_with("spring-boot-starter")) {
+        dependencies.insert(0, Coordinates::new("org.springframework.boot", "spring-boot-starter", "compile"));
+    }
+    match config.language.as_str() {
+        "kotlin" => {
+            dependencies.push(Coordinates::new("org.jetbrains.kotlin", "kotlin-reflect", "compile"));
+            dependencies.push(Coordinates::new("org.jetbrains.kotlin", "kotlin-test-junit5", "test"));
+        }
+        "groovy" => dependencies.push(Coordinates::new("org.apache.groovy", "groovy", "compile")),
+        _ => {}
+    }
+    if config.packaging == "war" {
+        dependencies.push(Coordinates::new("org.springframework.boot", "spring-boot-starter-tomcat", "provided"));
+    }
+    dependencies.push(Coordinates::new("org.springframework.boot", "spring-boot-starter-test", "test"));
+
+    repository_ids.sort();
+    repository_ids.dedup();
+    let repositories = repository_ids
+        .iter()
+        .filter_map(|id| snapshot.repositories.get(id).map(|r| (id.clone(), r.name.clone(), r.url.clone(), r.snapshot_enabled)))
+        .collect();
+    Ok(Build { dependencies, boms: boms.into_values().collect(), repositories })
+}
+
+/// DemoApplication for "demo", as Initializr derives it from the project name
+pub fn application_class(config: &SprintInitConfig) -> String {
+    let mut class: String = config
+        .name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| part[..1].to_uppercase() + &part[1..])
+        .collect();
+    if class.starts_with(|c: char| c.is_ascii_digit()) {
+        class.insert(0, '_');
+    }
+    class + "Application"
+}
+
+fn pom(config: &SprintInitConfig, build: &Build) -> String {
+    let mut pom = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    pom.push_str("<project xmlns=\"http://maven.apache.org/POM/4.0.0\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\n");
+    pom.push_str("\txsi:schemaLocation=\"http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd\">\n");
+    pom.push_str("\t<modelVersion>4.0.0</modelVersion>\n");
+    pom.push_str(&format!(
+        "\t<parent>\n\t\t<groupId>org.springframework.boot</groupId>\n\t\t<artifactId>spring-boot-starter-parent</artifactId>\n\t\t<version>{}</version>\n\t\t<relativePath/> <!-- lookup parent from repository -->\n\t</parent>\n",
+        config.boot_version
+    ));
+    pom.push_str(&format!("\t<groupId>{}</groupId>\n\t<artifactId>{}</artifactId>\n\t<version>{}</version>\n", xml(&config.group_id), xml(&config.artifact_id), xml(&config.version)));
+    if config.packaging == "war" {
+        pom.push_str("\t<packaging>war</packaging>\n");
+    }
+    pom.push_str(&format!("\t<name>{}</name>\n\t<description>{}</description>\n", xml(&config.name), xml(&config.description)));
+    pom.push_str(&format!("\t<properties>\n\t\t<java.version>{}</java.version>\n", config.java_version));
+    if config.language == "kotlin" {
+        pom.push_str(&format!("\t\t<kotlin.version>{}</kotlin.version>\n", KOTLIN_VERSION));
+    }
+    pom.push_str("\t</properties>\n\t<dependencies>\n");
+    for dependency in &build.dependencies {
+        pom.push_str(&format!(
+            "\t\t<dependency>\n\t\t\t<groupId>{}</groupId>\n\t\t\t<artifactId>{}</artifactId>\n",
+            dependency.group_id, dependency.artifact_id
+        ));
+        if let Some(version) = &dependency.version {
+            pom.push_str(&format!("\t\t\t<version>{}</version>\n", version));
+        }
+        match dependency.scope.as_str() {
+            "runtime" | "provided" | "test" => pom.push_str(&format!("\t\t\t<scope>{}</scope>\n", dependency.scope)),
+            "annotationProcessor" => pom.push_str("\t\t\t<optional>true</optional>\n"),
+            _ => {}
+        }
+        pom.push_str("\t\t</dependency>\n");
+    }
+    pom.push_str("\t</dependencies>\n");
+
+    if !build.boms.is_empty() {
+        pom.push_str("\t<dependencyManagement>\n\t\t<dependencies>\n");
+        for bom in &build.boms {
+            let parts: Vec<&str> = bom.split(':').collect();
+            pom.push_str(&format!(
+                "\t\t\t<dependency>\n\t\t\t\t<groupId>{}</groupId>\n\t\t\t\t<artifactId>{}</artifactId>\n\t\t\t\t<version>{}</version>\n\t\t\t\t<type>pom</type>\n\t\t\t\t<scope>import</scope>\n\t\t\t</dependency>\n",
+                parts[0], parts[1], parts[2]
+            ));
+        }
+        pom.push_str("\t\t</dependencies>\n\t</dependencyManagement>\n");
+    }
+
+    pom.push_str("\t<build>\n");
+    if config.language == "kotlin" {
+        pom.push_str("\t\t<sourceDirectory>${project.basedir}/src/main/kotlin</sourceDirectory>\n");
+        pom.push_str("\t\t<testSourceDirectory>${project.basedir}/src/test/kotlin</testSourceDirectory>\n");
+    }
+    pom.push_str("\t\t<plugins>\n");
+    match config.language.as_str() {
+        "kotlin" => pom.push_str(
+            "\t\t\t<plugin>\n\t\t\t\t<groupId>org.jetbrains.kotlin</groupId>\n\t\t\t\t<artifactId>kotlin-maven-plugin</artifactId>\n\t\t\t\t<configuration>\n\t\t\t\t\t<args>\n\t\t\t\t\t\t<arg>-Xjsr305=strict</arg>\n\t\t\t\t\t</args>\n\t\t\t\t\t<compilerPlugins>\n\t\t\t\t\t\t<plugin>spring</plugin>\n\t\t\t\t\t</compilerPlugins>\n\t\t\t\t</configuration>\n\t\t\t\t<dependencies>\n\t\t\t\t\t<dependency>\n\t\t\t\t\t\t<groupId>org.jetbrains.kotlin</groupId>\n\t\t\t\t\t\t<artifactId>kotlin-maven-allopen</artifactId>\n\t\t\t\t\t\t<version>${kotlin.version}</version>\n\t\t\t\t\t</dependency>\n\t\t\t\t</dependencies>\n\t\t\t</plugin>\n",
+        ),
+        "groovy" => pom.push_str(
+            "\t\t\t<plugin>\n\t\t\t\t<groupId>org.codehaus.gmavenplus</groupId>\n\t\t\t\t<artifactId>gmavenplus-plugin</artifactId>\n\t\t\t\t<version>4.2.1</version>\n\t\t\t\t<executions>\n\t\t\t\t\t<execution>\n\t\t\t\t\t\t<goals>\n\t\t\t\t\t\t\t<goal>addSources</goal>\n\t\t\t\t\t\t\t<goal>addTestSources</goal>\n\t\t\t\t\t\t\t<goal>compile</goal>\n\t\t\t\t\t\t\t<goal>compileTests</goal>\n\t\t\t\t\t\t</goals>\n\t\t\t\t\t</execution>\n\t\t\t\t</executions>\n\t\t\t</plugin>\n",
+        ),
+        _ => {}
+    }
+    pom.push_str("\t\t\t<plugin>\n\t\t\t\t<groupId>org.springframework.boot</groupId>\n\t\t\t\t<artifactId>spring-boot-maven-plugin</artifactId>\n\t\t\t</plugin>\n");
+    pom.push_str("\t\t</plugins>\n\t</build>\n");
+
+    if !build.repositories.is_empty() {
+        pom.push_str("\t<repositories>\n");
+        for (id, name, url, snapshots) in &build.repositories {
+            pom.push_str(&format!(
+                "\t\t<repository>\n\t\t\t<id>{}</id>\n\t\t\t<name>{}</name>\n\t\t\t<url>{}</url>\n\t\t\t<snapshots>\n\t\t\t\t<enabled>{}</enabled>\n\t\t\t</snapshots>\n\t\t</repository>\n",
+                id, name, url, snapshots
+            ));
+        }
+        pom.push_str("\t</repositories>\n");
+    }
+    pom.push_str("</project>\n");
+    pom
+}
+
+fn gradle(config: &SprintInitConfig, build: &Build, kotlin_dsl: bool) -> String {
+    let quote = |s: &str| if kotlin_dsl { format!("\"{}\"", s) } else { format!("'{}'", s) };
+    let plugin = |id: &str, version: Option<&str>| match (kotlin_dsl, version) {
+        (true, Some(version)) => format!("\tid(\"{}\") version \"{}\"\n", id, version),
+        (true, None) => format!("\t{}\n", id),
+        (false, Some(version)) => format!("\tid '{}' version '{}'\n", id, version),
+        (false, None) => format!("\tid '{}'\n", id),
+    };
+
+    let mut gradle = String::from("plugins {\n");
+    match config.language.as_str() {
+        "kotlin" => {
+            gradle.push_str(&plugin("org.jetbrains.kotlin.jvm", Some(KOTLIN_VERSION)));
+            gradle.push_str(&plugin("org.jetbrains.kotlin.plugin.spring", Some(KOTLIN_VERSION)));
+        }
+        "groovy" => gradle.push_str(&plugin("groovy", None)),
+        _ => gradle.push_str(&plugin("java", None)),
+    }
+    if config.packaging == "war" {
+        gradle.push_str(&plugin("war", None));
+    }
+    gradle.push_str(&plugin("org.springframework.boot", Some(&config.boot_version)));
+    gradle.push_str(&plugin("io.spring.dependency-management", Some(DEPENDENCY_MANAGEMENT_PLUGIN_VERSION)));
+    gradle.push_str("}\n\n");
+
+    gradle.push_str(&format!("group = {}\nversion = {}\ndescription = {}\n\n", quote(&config.group_id), quote(&config.version), quote(&config.description)));
+    gradle.push_str(&format!("java {{\n\ttoolchain {{\n\t\tlanguageVersion = JavaLanguageVersion.of({})\n\t}}\n}}\n\n", config.java_version));
+
+    gradle.push_str("repositories {\n\tmavenCentral()\n");
+    for (_, _, url, _) in &build.repositories {
+        if kotlin_dsl {
+            gradle.push_str(&format!("\tmaven {{ url = uri(\"{}\") }}\n", url));
+        } else {
+            gradle.push_str(&format!("\tmaven {{ url = '{}' }}\n", url));
+        }
+    }
+    gradle.push_str("}\n\n");
+
+    let line = |configuration: &str, notation: &str| {
+        if kotlin_dsl {
+            format!("\t{}(\"{}\")\n", configuration, notation)
+        } else {
+            format!("\t{} '{}'\n", configuration, notation)
+        }
+    };
+    gradle.push_str("dependencies {\n");
+    for dependency in &build.dependencies {
+        let notation = dependency.notation();
+        match dependency.scope.as_str() {
+            "runtime" => gradle.push_str(&line("runtimeOnly", &notation)),
+            "provided" if config.packaging == "war" => gradle.push_str(&line("providedRuntime", &notation)),
+            "provided" => gradle.push_str(&line("compileOnly", &notation)),
+            "test" => gradle.push_str(&line("testImplementation", &notation)),
+            "annotationProcessor" => {
+                gradle.push_str(&line("compileOnly", &notation));
+                gradle.push_str(&line("annotationProcessor", &notation));
+            }
+            _ => gradle.push_str(&line("implementation", &notation)),
+        }
+    }
+    gradle.push_str(&line("testRuntimeOnly", "org.junit.platform:junit-platform-launcher"));
+    gradle.push_str("}\n\n");
+
+    if !build.boms.is_empty() {
+        gradle.push_str("dependencyManagement {\n\timports {\n");
+        for bom in &build.boms {
+            if kotlin_dsl {
+                gradle.push_str(&format!("\t\tmavenBom(\"{}\")\n", bom));
+            } else {
+                gradle.push_str(&format!("\t\tmavenBom \"{}\"\n", bom));
+            }
+        }
+        gradle.push_str("\t}\n}\n\n");
+    }
+    if config.language == "kotlin" {
+        let args = if kotlin_dsl { "addAll(\"-Xjsr305=strict\")" } else { "addAll '-Xjsr305=strict'" };
+        gradle.push_str(&format!("kotlin {{\n\tcompilerOptions {{\n\t\tfreeCompilerArgs.{}\n\t}}\n}}\n\n", args));
+    }
+    if kotlin_dsl {
+        gradle.push_str("tasks.withType<Test> {\n\tuseJUnitPlatform()\n}\n");
+    } else {
+        gradle.push_str("tasks.named('test') {\n\tuseJUnitPlatform()\n}\n");
+    }
+    gradle
+}
+
+fn sources(config: &SprintInitConfig, class: &str) -> Vec<(String, String)> {
+    let package = &config.package_name;
+    let path = package.replace('.', "/");
+    let (dir, ext) = match config.language.as_str() {
+        "kotlin" => ("kotlin", "kt"),
+        "groovy" => ("groovy", "groovy"),
+        _ => ("java", "java"),
+    };
+    let semicolon = if config.language == "java" { ";" } else { "" };
+
+    let main = match config.language.as_str() {
+        "kotlin" => format!(
+            "package {package}\n\nimport org.springframework.boot.autoconfigure.SpringBootApplication\nimport org.springframework.boot.runApplication\n\n@SpringBootApplication\nclass {class}\n\nfun main(args: Array<String>) {{\n\trunApplication<{class}>(*args)\n}}\n"
+        ),
+        _ => format!(
+            "package {package}{semicolon}\n\nimport org.springframework.boot.SpringApplication{semicolon}\nimport org.springframework.boot.autoconfigure.SpringBootApplication{semicolon}\n\n@SpringBootApplication\n{public}class {class} {{\n\n\t{signature} {{\n\t\tSpringApplication.run({class}{literal}, args){semicolon}\n\t}}\n\n}}\n",
+            public = if config.language == "java" { "public " } else { "" },
+            signature = if config.language == "java" { "public static void main(String[] args)" } else { "static void main(String[] args)" },
+            literal = if config.language == "java" { ".class" } else { "" },
+        ),
+    };
+    let test = match config.language.as_str() {
+        "kotlin" => format!(
+            "package {package}\n\nimport org.junit.jupiter.api.Test\nimport org.springframework.boot.test.context.SpringBootTest\n\n@SpringBootTest\nclass {class}Tests {{\n\n\t@Test\n\tfun contextLoads() {{\n\t}}\n\n}}\n"
+        ),
+        _ => format!(
+            "package {package}{semicolon}\n\nimport org.junit.jupiter.api.Test{semicolon}\nimport org.springframework.boot.test.context.SpringBootTest{semicolon}\n\n@SpringBootTest\nclass {class}Tests {{\n\n\t@Test\n\tvoid contextLoads() {{\n\t}}\n\n}}\n"
+        ),
+    };
+
+    let mut files = vec![
+        (format!("src/main/{}/{}/{}.{}", dir, path, class, ext), main),
+        (format!("src/test/{}/{}/{}Tests.{}", dir, path, class, ext), test),
+    ];
+    if config.packaging == "war" {
+        let initializer = match config.language.as_str() {
+            "kotlin" => format!(
+                "package {package}\n\nimport org.springframework.boot.builder.SpringApplicationBuilder\nimport org.springframework.boot.web.servlet.support.SpringBootServletInitializer\n\nclass ServletInitializer : SpringBootServletInitializer() {{\n\n\toverride fun configure(application: SpringApplicationBuilder): SpringApplicationBuilder {{\n\t\treturn application.sources({class}::class.java)\n\t}}\n\n}}\n"
+            ),
+            _ => format!(
+                "package {package}{semicolon}\n\nimport org.springframework.boot.builder.SpringApplicationBuilder{semicolon}\nimport org.springframework.boot.web.servlet.support.SpringBootServletInitializer{semicolon}\n\n{public}class ServletInitializer extends SpringBootServletInitializer {{\n\n\t@Override\n\tprotected SpringApplicationBuilder configure(SpringApplicationBuilder application) {{\n\t\treturn application.sources({class}{literal}){semicolon}\n\t}}\n\n}}\n",
+                public = if config.language == "java" { "public " } else { "" },
+                literal = if config.language == "java" { ".class" } else { "" },
+            ),
+        };
+        files.push((format!("src/main/{}/{}/ServletInitializer.{}", dir, path, ext), initializer));
+    }
+    files
+}
+
+const GITIGNORE: &str = "HELP.md\ntarget/\nbuild/\n.gradle\n!gradle/wrapper/gradle-wrapper.jar\n!**/src/main/**/target/\n!**/src/test/**/target/\n!**/src/main/**/build/\n!**/src/test/**/build/\n\n### STS ###\n.apt_generated\n.classpath\n.factorypath\n.project\n.settings\n.springBeans\n.sts4-cache\n\n### IntelliJ IDEA ###\n.idea\n*.iws\n*.iml\n*.ipr\nout/\n\n### VS Code ###\n.vscode/\n";
+
+/// Files of the project for `config`, project relative paths with their content
+pub fn files(config: &SprintInitConfig, snapshot: &InitializrDependencies) -> anyhow::Result<Vec<(String, String)>> {
+    let build = resolve(config, snapshot)?;
+    let mut files = vec![(".gitignore".to_string(), GITIGNORE.to_string())];
+    match config.project_type.as_str() {
+        "maven-project" => {
+            files.push(("pom.xml".to_string(), pom(config, &build)));
+            files.push((
+                ".mvn/wrapper/maven-wrapper.properties".to_string(),
+                format!(
+                    "wrapperVersion={}\ndistributionType=only-script\ndistributionUrl=https://repo.maven.apache.org/maven2/org/apache/maven/apache-maven/{}/apache-maven-{}-bin.zip\n",
+                    MAVEN_WRAPPER_VERSION, MAVEN_VERSION, MAVEN_VERSION
+                ),
+            ));
+        }
+        "gradle-project" | "gradle-project-kotlin" => {
+            let kotlin_dsl = config.project_type == "gradle-project-kotlin";
+            let (build_file, settings_file, name) = if kotlin_dsl {
+                ("build.gradle.kts", "settings.gradle.kts", format!("\"{}\"", config.artifact_id))
+            } else {
+                ("build.gradle", "settings.gradle", format!("'{}'", config.artifact_id))
+            };
+            files.push((build_file.to_string(), gradle(config, &build, kotlin_dsl)));
+            files.push((settings_file.to_string(), format!("rootProject.name = {}\n", name)));
+            files.push((
+                "gradle/wrapper/gradle-wrapper.properties".to_string(),
+                format!(
+                    "distributionBase=GRADLE_USER_HOME\ndistributionPath=wrapper/dists\ndistributionUrl=https\\://services.gradle.org/distributions/gradle-{}-bin.zip\nnetworkTimeout=10000\nvalidateDistributionUrl=true\nzipStoreBase=GRADLE_USER_HOME\nzipStorePath=wrapper/dists\n",
+                    GRADLE_VERSION
+                ),
+            ));
+        }
+        other => anyhow::bail!("The offline backend cannot generate '{}' projects", other),
+    }
+
+    files.extend(sources(config, &application_class(config)));
+    let resources = if config.configuration_file_format == "yaml" {
+        ("src/main/resources/application.yaml".to_string(), format!("spring:\n  application:\n    name: {}\n", config.name))
+    } else {
+        ("src/main/resources/application.properties".to_string(), format!("spring.application.name={}\n", config.name))
+    };
+    files.push(resources);
+    Ok(files)
+}
+
+/// The project for `config` as a zip laid out like a starter.zip, files at the root
+pub fn archive(config: &SprintInitConfig, snapshot: &InitializrDependencies) -> anyhow::Result<Vec<u8>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (path, content) in files(config, snapshot)? {
+        writer.start_file(path, zip::write::SimpleFileOptions::default())?;
+        writer.write_all(content.as_bytes())?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::api::{Bom, Dependency, Repository};
+    use std::collections::HashMap;
+
+    fn snapshot() -> InitializrDependencies {
+        let dependency = |group_id: &str, artifact_id: &str, scope: &str, bom: Option<&str>| Dependency {
+            group_id: group_id.to_string(),
+            artifact_id: artifact_id.to_string(),
+            scope: scope.to_string(),
+            bom: bom.map(String::from),
+            version: None,
+            repository: None,
+        };
+        InitializrDependencies {
+            boot_version: "3.5.0".to_string(),
+            dependencies: HashMap::from([
+                ("web".to_string(), dependency("org.springframework.boot", "spring-boot-starter-web", "compile", None)),
+                ("lombok".to_string(), dependency("org.projectlombok", "lombok", "annotationProcessor", None)),
+                ("cloud-config-client".to_string(), dependency("org.springframework.cloud", "spring-cloud-starter-config", "compile", Some("spring-cloud"))),
+            ]),
+            boms: HashMap::from([(
+                "spring-cloud".to_string(),
+                Bom {
+                    group_id: "org.springframework.cloud".to_string(),
+                    artifact_id: "spring-cloud-dependencies".to_string(),
+                    version: "2025.0.0".to_string(),
+                    repositories: vec!["spring-milestones".to_string()],
+                },
+            )]),
+            repositories: HashMap::from([(
+                "spring-milestones".to_string(),
+                Repository { name: "Spring Milestones".to_string(), url: "https://repo.spring.io/milestone".to_string(), snapshot_enabled: false },
+            )]),
+        }
+    }
+
+    #[test]
+    fn maven_project_follows_the_initializr_layout() {
+        let config = SprintInitConfig {
+            boot_version: "3.5.0".to_string(),
+            dependencies: "web,lombok,cloud-config-client".to_string(),
+            ..Default::default()
+        };
+        let files: HashMap<String, String> = files(&config, &snapshot()).unwrap().into_iter().collect();
+
+        let pom = &files["pom.xml"];
+        assert!(pom.contains("<artifactId>spring-boot-starter-parent</artifactId>\n\t\t<version>3.5.0</version>"));
+        assert!(pom.contains("<artifactId>lombok</artifactId>\n\t\t\t<optional>true</optional>"));
+        assert!(pom.contains("<artifactId>spring-cloud-dependencies</artifactId>\n\t\t\t\t<version>2025.0.0</version>"));
+        assert!(pom.contains("<url>https://repo.spring.io/milestone</url>"));
+        assert!(!pom.contains("<artifactId>spring-boot-starter</artifactId>"));
+        assert!(files["src/main/java/com/example/demo/DemoApplication.java"].contains("SpringApplication.run(DemoApplication.class, args);"));
+        assert!(files.contains_key("src/test/java/com/example/demo/DemoApplicationTests.java"));
+        assert!(files.contains_key(".mvn/wrapper/maven-wrapper.properties"));
+        assert_eq!(files["src/main/resources/application.properties"], "spring.application.name=demo\n");
+        assert!(zip::ZipArchive::new(Cursor::new(archive(&config, &snapshot()).unwrap())).unwrap().by_name("pom.xml").is_ok());
+    }
+
+    #[test]
+    fn gradle_kotlin_project_uses_the_kotlin_dsl() {
+        let config = SprintInitConfig {
+            project_type: "gradle-project-kotlin".to_string(),
+            language: "kotlin".to_string(),
+            boot_version: "3.5.0".to_string(),
+            name: "order service".to_string(),
+            dependencies: "lombok".to_string(),
+            ..Default::default()
+        };
+        let files: HashMap<String, String> = files(&config, &snapshot()).unwrap().into_iter().collect();
+
+        let build = &files["build.gradle.kts"];
+        assert!(build.contains("\tid(\"org.springframework.boot\") version \"3.5.0\"\n"));
+        assert!(build.contains("\timplementation(\"org.springframework.boot:spring-boot-starter\")\n"));
+        assert!(build.contains("\tannotationProcessor(\"org.projectlombok:lombok\")\n"));
+        assert_eq!(files["settings.gradle.kts"], "rootProject.name = \"demo\"\n");
+        assert!(files["src/main/kotlin/com/example/demo/OrderServiceApplication.kt"].contains("runApplication<OrderServiceApplication>(*args)"));
+        assert!(super::files(&SprintInitConfig { dependencies: "nope".to_string(), ..Default::default() }, &snapshot()).is_err());
+    }
+}
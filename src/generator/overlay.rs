@@ -0,0 +1,265 @@
+// template overlays: directories of files copied into an extracted project, with `{{variable}}`
+// placeholders in paths and contents filled from the project config. An optional overlay.toml at
+// the root of the overlay decides what happens when a file already exists in the project:
+//
+//   default = "skip"
+//   [files]
+//   ".gitignore" = "append"
+//   ".github/" = "overwrite"      # trailing slash: everything below that directory
+use crate::config::util::get_application_config_dir;
+use crate::types::generic::SprintInitConfig;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const OVERLAY_MANIFEST: &str = "overlay.toml";
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// keep the generated file
+    #[default]
+    Skip,
+    Overwrite,
+    /// add the overlay content after the generated content
+    Append,
+    /// refuse to apply the overlay
+    Fail,
+}
+
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Manifest {
+    pub default: ConflictPolicy,
+    pub files: BTreeMap<String, ConflictPolicy>,
+}
+
+impl Manifest {
+    /// Policy for `file` (relative, before rendering); the longest matching entry wins
+    pub fn policy(&self, file: &str) -> ConflictPolicy {
+        self.files
+            .iter()
+            .filter(|(pattern, _)| *pattern == file || (pattern.ends_with('/') && file.starts_with(pattern.as_str())))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, policy)| *policy)
+            .unwrap_or(self.default)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OverlayReport {
+    pub written: Vec<String>,
+    pub appended: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Directory named overlays are looked up in: <config dir>/overlays
+pub fn overlays_dir() -> PathBuf {
+    get_application_config_dir().join("overlays")
+}
+
+/// Names of the overlays in overlays_dir, sorted
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(overlays_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().is_dir())
+                .filter_map(|e| e.file_name().to_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// `overlay` is either a directory path or the name of a directory in overlays_dir
+pub fn locate(overlay: &str) -> anyhow::Result<PathBuf> {
+    let path = Path::new(overlay);
+    if path.is_dir() {
+        return Ok(path.to_path_buf());
+    }
+    let named = overlays_dir().join(overlay);
+    if named.is_dir() {
+        return Ok(named);
+    }
+    anyhow::bail!("Overlay '{}' not found, expected a directory or a folder in {}", overlay, overlays_dir().display())
+}
+
+/// Values available as `{{name}}` in overlay paths and files
+pub fn variables(config: &SprintInitConfig) -> Vec<(&'static str, String)> {
+    let build_tool = if config.project_type.starts_with("gradle") { "gradle" } else { "maven" };
+    vec![
+        ("groupId", config.group_id.clone()),
+        ("artifactId", config.artifact_id.clone()),
+        ("name", config.name.clone()),
+        ("description", config.description.clone()),
+        ("version", config.version.clone()),
+        ("packageName", config.package_name.clone()),
+        ("packagePath", config.package_name.replace('.', "/")),
+        ("javaVersion", config.java_version.to_string()),
+        ("bootVersion", config.boot_version.clone()),
+        ("language", config.language.clone()),
+        ("packaging", config.packaging.clone()),
+        ("projectType", config.project_type.clone()),
+        ("buildTool", build_tool.to_string()),
+    ]
+}
+
+/// Replace the known `{{name}}` placeholders; anything else, like `${{ github.sha }}`, is left alone
+pub fn render(template: &str, variables: &[(&str, String)]) -> String {
+    variables
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{{{}}}}}", name), value))
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<String>) -> anyhow::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if relative != OVERLAY_MANIFEST {
+                files.push(relative);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy the overlay at `overlay` into `project`, rendered for `config`.
+/// Conflicts under the fail policy are reported before anything is written.
+pub fn apply(overlay: &Path, project: &Path, config: &SprintInitConfig) -> anyhow::Result<OverlayReport> {
+    let manifest_path = overlay.join(OVERLAY_MANIFEST);
+    let manifest: Manifest = if manifest_path.is_file() {
+        toml::from_str(&fs::read_to_string(&manifest_path)?)
+            .map_err(|e| anyhow::anyhow!("Invalid {}: {}", manifest_path.display(), e))?
+    } else {
+        Manifest::default()
+    };
+    let variables = variables(config);
+
+    let mut files = Vec::new();
+    collect_files(overlay, overlay, &mut files)?;
+    files.sort();
+    let planned: Vec<(String, String, ConflictPolicy)> = files
+        .into_iter()
+        .map(|file| {
+            let target = render(&file, &variables);
+            let policy = manifest.policy(&file);
+            (file, target, policy)
+        })
+        .collect();
+
+    let conflicts: Vec<&str> = planned
+        .iter()
+        .filter(|(_, target, policy)| *policy == ConflictPolicy::Fail && project.join(target).exists())
+        .map(|(_, target, _)| target.as_str())
+        .collect();
+    if !conflicts.is_empty() {
+        anyhow::bail!("Overlay {} would replace {}", overlay.display(), conflicts.join(", "));
+    }
+
+    let mut report = OverlayReport::default();
+    for (file, target, policy) in planned {
+        let source = overlay.join(&file);
+        let destination = project.join(&target);
+        let exists = destination.exists();
+        if exists && policy == ConflictPolicy::Skip {
+            report.skipped.push(target);
+            continue;
+        }
+
+        // binary files are copied as they are
+        let bytes = fs::read(&source)?;
+        let content = match String::from_utf8(bytes) {
+            Ok(text) => render(&text, &variables).into_bytes(),
+            Err(e) => e.into_bytes(),
+        };
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if exists && policy == ConflictPolicy::Append {
+            let mut current = fs::read(&destination)?;
+            if !current.is_empty() && !current.ends_with(b"\n") {
+                current.push(b'\n');
+            }
+            current.extend(content);
+            fs::write(&destination, current)?;
+            report.appended.push(target);
+        } else {
+            fs::write(&destination, content)?;
+            fs::set_permissions(&destination, fs::metadata(&source)?.permissions())?;
+            report.written.push(target);
+        }
+    }
+    Ok(report)
+}
+
+/// Apply every overlay selected in `config` to the project in `project`, in order
+pub fn apply_all(project: &Path, config: &SprintInitConfig) -> anyhow::Result<Vec<(String, OverlayReport)>> {
+    config
+        .overlays
+        .iter()
+        .map(|name| Ok((name.clone(), apply(&locate(name)?, project, config)?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("spring-tui-overlay-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn renders_paths_and_contents_with_per_file_policies() {
+        let overlay = temp("source");
+        let project = temp("project");
+        fs::create_dir_all(overlay.join("src/main/java/{{packagePath}}")).unwrap();
+        fs::write(overlay.join("src/main/java/{{packagePath}}/Banner.java"), "package {{packageName}};\n").unwrap();
+        fs::write(overlay.join("Dockerfile"), "FROM eclipse-temurin:{{javaVersion}}\n# ${{ github.sha }}\n").unwrap();
+        fs::write(overlay.join(".gitignore"), ".env\n").unwrap();
+        fs::write(overlay.join("HELP.md"), "ours\n").unwrap();
+        fs::write(overlay.join(OVERLAY_MANIFEST), "[files]\n\".gitignore\" = \"append\"\n").unwrap();
+        fs::write(project.join(".gitignore"), "target/").unwrap();
+        fs::write(project.join("HELP.md"), "generated\n").unwrap();
+
+        let config = SprintInitConfig { java_version: 21, ..Default::default() };
+        let report = apply(&overlay, &project, &config).unwrap();
+
+        let read = |f: &str| fs::read_to_string(project.join(f)).unwrap();
+        assert_eq!(read("src/main/java/com/example/demo/Banner.java"), "package com.example.demo;\n");
+        assert_eq!(read("Dockerfile"), "FROM eclipse-temurin:21\n# ${{ github.sha }}\n");
+        assert_eq!(read(".gitignore"), "target/\n.env\n");
+        assert_eq!(read("HELP.md"), "generated\n");
+        assert_eq!(report.skipped, vec!["HELP.md"]);
+        assert!(!project.join(OVERLAY_MANIFEST).exists());
+    }
+
+    #[test]
+    fn fail_policy_aborts_before_writing() {
+        let overlay = temp("strict");
+        let project = temp("strict-project");
+        fs::write(overlay.join("Dockerfile"), "FROM x\n").unwrap();
+        fs::write(overlay.join("pom.xml"), "<project/>\n").unwrap();
+        fs::write(overlay.join(OVERLAY_MANIFEST), "default = \"fail\"\n").unwrap();
+        fs::write(project.join("pom.xml"), "generated").unwrap();
+
+        let result = apply(&overlay, &project, &SprintInitConfig::default());
+
+        assert!(result.unwrap_err().to_string().contains("pom.xml"));
+        assert!(!project.join("Dockerfile").exists());
+        assert_eq!(
+            Manifest { default: ConflictPolicy::Skip, files: BTreeMap::from([(".github/".to_string(), ConflictPolicy::Overwrite)]) }
+                .policy(".github/workflows/ci.yml"),
+            ConflictPolicy::Overwrite
+        );
+    }
+}
@@ -0,0 +1,170 @@
+// edits of the extracted project's application config, in .properties or .yaml following
+// SprintInitConfig.configuration_file_format. Recipes only add missing keys, application
+// settings replace what Initializr generated
+use crate::types::generic::SprintInitConfig;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Set `properties` in a properties file; existing keys are only replaced when `overwrite` is set
+pub fn merge_properties(content: &str, properties: &[(String, String)], overwrite: bool) -> String {
+    let key_of = |line: &str| {
+        let key = line.split(['=', ':']).next().unwrap_or("").trim().to_string();
+        (!key.is_empty() && !key.starts_with('#') && !key.starts_with('!')).then_some(key)
+    };
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    for (key, value) in properties {
+        match lines.iter().position(|l| key_of(l).as_deref() == Some(key.as_str())) {
+            Some(i) if overwrite => lines[i] = format!("{}={}", key, value),
+            Some(_) => {}
+            None => lines.push(format!("{}={}", key, value)),
+        }
+    }
+    let mut merged = lines.join("\n");
+    if !merged.is_empty() {
+        merged.push('\n');
+    }
+    merged
+}
+
+/// Set `properties` in a yaml document, nesting their dotted keys
+pub fn merge_yaml(content: &str, properties: &[(String, String)], overwrite: bool) -> anyhow::Result<String> {
+    use serde_yaml::{Mapping, Value};
+
+    let mut root = match serde_yaml::from_str::<Value>(content)? {
+        Value::Mapping(mapping) => mapping,
+        Value::Null => Mapping::new(),
+        _ => anyhow::bail!("application yaml is not a mapping"),
+    };
+    for (key, value) in properties {
+        let mut node = &mut root;
+        let mut parts = key.split('.').peekable();
+        while let Some(part) = parts.next() {
+            let part = Value::String(part.to_string());
+            if parts.peek().is_none() {
+                if overwrite || !node.contains_key(&part) {
+                    // keep numbers and booleans unquoted
+                    let scalar = match serde_yaml::from_str::<Value>(value) {
+                        Ok(v @ (Value::Number(_) | Value::Bool(_))) => v,
+                        _ => Value::String(value.clone()),
+                    };
+                    node.insert(part, scalar);
+                }
+                break;
+            }
+            let child = node.entry(part).or_insert_with(|| Value::Mapping(Mapping::new()));
+            match child {
+                Value::Mapping(mapping) => node = mapping,
+                // a scalar already sits where the key would go, leave it to the user
+                _ => break,
+            }
+        }
+    }
+    Ok(serde_yaml::to_string(&root)?)
+}
+
+/// application.properties / application-<profile>.yaml ... for `config`, the existing .yml spelling included
+fn application_file(project: &Path, config: &SprintInitConfig, profile: Option<&str>) -> PathBuf {
+    let resources = project.join("src/main/resources");
+    let stem = match profile {
+        Some(profile) => format!("application-{}", profile),
+        None => "application".to_string(),
+    };
+    if config.configuration_file_format == "yaml" {
+        let yml = resources.join(format!("{}.yml", stem));
+        if yml.exists() { yml } else { resources.join(format!("{}.yaml", stem)) }
+    } else {
+        resources.join(format!("{}.properties", stem))
+    }
+}
+
+/// Write `properties` into the application file of `profile` (the main one when None)
+pub fn apply(
+    project: &Path,
+    config: &SprintInitConfig,
+    profile: Option<&str>,
+    properties: &[(String, String)],
+    overwrite: bool,
+) -> anyhow::Result<()> {
+    if properties.is_empty() {
+        return Ok(());
+    }
+    let path = application_file(project, config, profile);
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    let merged = if config.configuration_file_format == "yaml" {
+        merge_yaml(&content, properties, overwrite)?
+    } else {
+        merge_properties(&content, properties, overwrite)
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, merged)?;
+    Ok(())
+}
+
+/// Write the application settings of `config`, replacing what Initializr generated
+pub fn apply_settings(project: &Path, config: &SprintInitConfig) -> anyhow::Result<()> {
+    let settings = &config.application;
+    apply(project, config, None, &settings.main_properties(), true)?;
+    for (profile, properties) in &settings.profiles {
+        let properties: Vec<(String, String)> = properties.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+        apply(project, config, Some(profile), &properties, true)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn properties_are_added_and_replaced_on_request() {
+        let properties = vec![
+            ("spring.application.name".to_string(), "other".to_string()),
+            ("spring.datasource.url".to_string(), "jdbc:postgresql://localhost:5432/demo".to_string()),
+        ];
+
+        let merged = merge_properties("spring.application.name=demo", &properties, false);
+        assert_eq!(merged, "spring.application.name=demo\nspring.datasource.url=jdbc:postgresql://localhost:5432/demo\n");
+        let replaced = merge_properties("# app\nspring.application.name=demo\n", &properties[..1], true);
+        assert_eq!(replaced, "# app\nspring.application.name=other\n");
+
+        let yaml = merge_yaml("spring:\n  application:\n    name: demo\n", &properties, false).unwrap();
+        let value: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(value["spring"]["application"]["name"].as_str(), Some("demo"));
+        assert_eq!(value["spring"]["datasource"]["url"].as_str(), Some("jdbc:postgresql://localhost:5432/demo"));
+    }
+
+    #[test]
+    fn yaml_settings_keep_numbers_and_booleans_unquoted() {
+        let properties = vec![
+            ("server.port".to_string(), "8081".to_string()),
+            ("spring.jpa.show-sql".to_string(), "true".to_string()),
+            ("spring.application.name".to_string(), "orders".to_string()),
+        ];
+
+        let yaml = merge_yaml("", &properties, true).unwrap();
+        assert_eq!(yaml, "server:\n  port: 8081\nspring:\n  jpa:\n    show-sql: true\n  application:\n    name: orders\n");
+    }
+
+    #[test]
+    fn settings_go_to_main_and_profile_files() {
+        let project = std::env::temp_dir().join(format!("spring-tui-properties-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&project);
+        let resources = project.join("src/main/resources");
+        fs::create_dir_all(&resources).unwrap();
+        fs::write(resources.join("application.yaml"), "spring:\n  application:\n    name: demo\n").unwrap();
+
+        let mut config = SprintInitConfig { configuration_file_format: "yaml".to_string(), ..Default::default() };
+        config.application.server_port = Some(8081);
+        config.application.application_name = Some("orders".to_string());
+        config.application.profiles.insert("dev".to_string(), [("logging.level.root".to_string(), "debug".to_string())].into());
+        apply_settings(&project, &config).unwrap();
+
+        let main: serde_yaml::Value = serde_yaml::from_str(&fs::read_to_string(resources.join("application.yaml")).unwrap()).unwrap();
+        assert_eq!(main["spring"]["application"]["name"].as_str(), Some("orders"));
+        assert_eq!(main["server"]["port"].as_u64(), Some(8081));
+        let dev = fs::read_to_string(resources.join("application-dev.yaml")).unwrap();
+        assert_eq!(dev, "logging:\n  level:\n    root: debug\n");
+    }
+}
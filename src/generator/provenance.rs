@@ -0,0 +1,128 @@
+// provenance: the SprintInitConfig a project was generated from, recorded as .spring-tui.yaml in
+// the extracted project and as the comment of the downloaded archive. The file is read back as a
+// directory config layer (see config::layers), so spring-tui run inside the project starts from
+// its settings, and `upgrade` / `add-deps` take everything but the dependencies and Boot
+// version from it. Config paths ending in .zip read the archive comment (config::util::read_source).
+use crate::config::migrate;
+use crate::types::config::FileType;
+use crate::types::generic::SprintInitConfig;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::OnceLock;
+
+pub const PROVENANCE_FILE: &str = ".spring-tui.yaml";
+
+const HEADER: &str = "# written by spring-tui: the config this project was generated from\n";
+
+static ENABLED: OnceLock<bool> = OnceLock::new();
+
+/// Whether generations record their config, on unless turned off; only the first call has an effect
+pub fn set_enabled(enabled: bool) {
+    let _ = ENABLED.set(enabled);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.get().copied().unwrap_or(true)
+}
+
+/// The yaml document recorded for `config`
+pub fn document(config: &SprintInitConfig) -> anyhow::Result<String> {
+    Ok(format!("{}{}", HEADER, serde_yaml::to_string(config)?))
+}
+
+/// Whether `content` records a project config rather than directory defaults,
+/// which never carry a `configVersion`
+pub fn is_provenance(content: &str) -> bool {
+    matches!(
+        serde_yaml::from_str::<serde_yaml::Value>(content),
+        Ok(serde_yaml::Value::Mapping(mapping)) if mapping.contains_key("configVersion")
+    )
+}
+
+/// Parse a recorded config, migrating ones written by older versions
+pub fn parse(content: &str) -> anyhow::Result<SprintInitConfig> {
+    Ok(migrate::migrate(migrate::parse_value(content, &FileType::Yaml)?)?.config)
+}
+
+/// Config recorded in `project`, None when it has no provenance file
+pub fn read(project: &Path) -> anyhow::Result<Option<SprintInitConfig>> {
+    let Ok(content) = fs::read_to_string(project.join(PROVENANCE_FILE)) else {
+        return Ok(None);
+    };
+    if !is_provenance(&content) {
+        return Ok(None);
+    }
+    parse(&content).map(Some)
+}
+
+/// Record `config` in `project`. A .spring-tui.yaml holding directory defaults is left alone;
+/// returns whether the file was written.
+pub fn write(project: &Path, config: &SprintInitConfig) -> anyhow::Result<bool> {
+    let path = project.join(PROVENANCE_FILE);
+    if fs::read_to_string(&path).is_ok_and(|existing| !is_provenance(&existing)) {
+        return Ok(false);
+    }
+    fs::write(&path, document(config)?)?;
+    Ok(true)
+}
+
+/// `archive` with the config document as its zip comment
+pub fn with_comment(archive: Vec<u8>, config: &SprintInitConfig) -> anyhow::Result<Vec<u8>> {
+    let mut writer = zip::ZipWriter::new_append(Cursor::new(archive))?;
+    writer.set_comment(document(config)?);
+    let cursor = writer.finish()?;
+    // the new central directory may end before an old, longer comment did
+    let end = cursor.position() as usize;
+    let mut archive = cursor.into_inner();
+    archive.truncate(end);
+    Ok(archive)
+}
+
+/// Config document in the comment of the archive at `path`
+pub fn archive_comment(path: &Path) -> anyhow::Result<String> {
+    let archive = zip::ZipArchive::new(fs::File::open(path)?)
+        .map_err(|e| anyhow::anyhow!("{} is not a zip archive: {}", path.display(), e))?;
+    let comment = String::from_utf8_lossy(archive.comment()).to_string();
+    if !is_provenance(&comment) {
+        anyhow::bail!("{} was not generated by spring-tui, its comment holds no config", path.display());
+    }
+    Ok(comment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn config_round_trips_through_the_archive_comment() {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("demo/pom.xml", zip::write::SimpleFileOptions::default()).unwrap();
+        writer.write_all(b"<project/>").unwrap();
+        let archive = writer.finish().unwrap().into_inner();
+        let config = SprintInitConfig { artifact_id: "orders".to_string(), overlays: vec!["service".to_string()], ..Default::default() };
+
+        let path = std::env::temp_dir().join(format!("spring-tui-provenance-{}.zip", std::process::id()));
+        fs::write(&path, with_comment(archive, &config).unwrap()).unwrap();
+
+        assert_eq!(parse(&archive_comment(&path).unwrap()).unwrap(), config);
+        let mut reopened = zip::ZipArchive::new(fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(reopened.by_name("demo/pom.xml").unwrap().size(), 10);
+    }
+
+    #[test]
+    fn directory_defaults_are_not_overwritten() {
+        let project = std::env::temp_dir().join(format!("spring-tui-provenance-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&project);
+        fs::create_dir_all(&project).unwrap();
+        let config = SprintInitConfig::default();
+
+        assert!(write(&project, &config).unwrap());
+        assert_eq!(read(&project).unwrap(), Some(config.clone()));
+
+        fs::write(project.join(PROVENANCE_FILE), "groupId: com.acme\n").unwrap();
+        assert!(!write(&project, &config).unwrap());
+        assert_eq!(read(&project).unwrap(), None);
+    }
+}
@@ -0,0 +1,252 @@
+// recipes: setup that only makes sense for some starters, applied to the extracted project when
+// all of their trigger dependencies are selected. Built-in recipes live below; user recipes are
+// directories in <config dir>/recipes (and any `recipeDirs`), one per recipe:
+//
+//   jooq-codegen/
+//     recipe.toml       when = ["jooq", "postgresql"], optional language = ["java"], [properties]
+//     files/...         copied like an overlay, `{{variable}}` placeholders included
+//
+// A user recipe with the name of a built-in one replaces it. Existing files and properties are kept.
+use super::{overlay, properties};
+use crate::config::util::get_application_config_dir;
+use crate::types::generic::SprintInitConfig;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+pub const RECIPE_MANIFEST: &str = "recipe.toml";
+
+static RECIPE_DIRS: OnceLock<Vec<PathBuf>> = OnceLock::new();
+
+/// Extra directories to load user recipes from; only the first call has an effect
+pub fn set_recipe_dirs(dirs: &str) {
+    let _ = RECIPE_DIRS.set(dirs.split(',').map(str::trim).filter(|d| !d.is_empty()).map(PathBuf::from).collect());
+}
+
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+struct Manifest {
+    description: String,
+    when: Vec<String>,
+    language: Vec<String>,
+    properties: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recipe {
+    pub name: String,
+    pub description: String,
+    /// dependency ids that must all be selected
+    pub when: Vec<String>,
+    /// languages the recipe applies to, any when empty
+    pub language: Vec<String>,
+    /// spring properties, dotted keys
+    pub properties: BTreeMap<String, String>,
+    /// project relative path (may contain placeholders) and content
+    pub files: Vec<(String, Vec<u8>)>,
+    /// directory the recipe was loaded from, None for built-in ones
+    pub source: Option<PathBuf>,
+}
+
+impl Recipe {
+    pub fn applies_to(&self, config: &SprintInitConfig) -> bool {
+        let selected: Vec<&str> = config.dependencies.split(',').map(str::trim).collect();
+        !self.when.is_empty()
+            && self.when.iter().all(|id| selected.contains(&id.as_str()))
+            && (self.language.is_empty() || self.language.contains(&config.language))
+    }
+}
+
+fn builtin_recipe(name: &str, description: &str, when: &[&str], language: &[&str]) -> Recipe {
+    Recipe {
+        name: name.to_string(),
+        description: description.to_string(),
+        when: when.iter().map(|s| s.to_string()).collect(),
+        language: language.iter().map(|s| s.to_string()).collect(),
+        properties: BTreeMap::new(),
+        files: Vec::new(),
+        source: None,
+    }
+}
+
+const SECURITY_JAVA: &str = r#"package {{packageName}};
+
+import org.springframework.context.annotation.Bean;
+import org.springframework.context.annotation.Configuration;
+import org.springframework.security.config.Customizer;
+import org.springframework.security.config.annotation.web.builders.HttpSecurity;
+import org.springframework.security.web.SecurityFilterChain;
+
+@Configuration
+public class SecurityConfig {
+
+    @Bean
+    SecurityFilterChain securityFilterChain(HttpSecurity http) throws Exception {
+        http
+            .authorizeHttpRequests(requests -> requests
+                .requestMatchers("/actuator/health").permitAll()
+                .anyRequest().authenticated())
+            .httpBasic(Customizer.withDefaults());
+        return http.build();
+    }
+}
+"#;
+
+const SECURITY_KOTLIN: &str = r#"package {{packageName}}
+
+import org.springframework.context.annotation.Bean
+import org.springframework.context.annotation.Configuration
+import org.springframework.security.config.Customizer
+import org.springframework.security.config.annotation.web.builders.HttpSecurity
+import org.springframework.security.web.SecurityFilterChain
+
+@Configuration
+class SecurityConfig {
+
+    @Bean
+    fun securityFilterChain(http: HttpSecurity): SecurityFilterChain {
+        http
+            .authorizeHttpRequests { requests ->
+                requests
+                    .requestMatchers("/actuator/health").permitAll()
+                    .anyRequest().authenticated()
+            }
+            .httpBasic(Customizer.withDefaults())
+        return http.build()
+    }
+}
+"#;
+
+/// Recipes shipped with spring-tui
+pub fn builtin() -> Vec<Recipe> {
+    let mut flyway = builtin_recipe("flyway", "First Flyway migration", &["flyway"], &[]);
+    flyway.files.push((
+        "src/main/resources/db/migration/V1__init.sql".to_string(),
+        b"-- initial schema for {{artifactId}}\n".to_vec(),
+    ));
+
+    let mut postgres = builtin_recipe("jpa-postgresql", "Local PostgreSQL datasource", &["data-jpa", "postgresql"], &[]);
+    postgres.properties = BTreeMap::from([
+        ("spring.datasource.url".to_string(), "jdbc:postgresql://localhost:5432/{{artifactId}}".to_string()),
+        ("spring.datasource.username".to_string(), "postgres".to_string()),
+        ("spring.datasource.password".to_string(), "postgres".to_string()),
+    ]);
+
+    let mut security_java = builtin_recipe("security-java", "Basic SecurityFilterChain", &["security"], &["java"]);
+    security_java.files.push(("src/main/java/{{packagePath}}/SecurityConfig.java".to_string(), SECURITY_JAVA.as_bytes().to_vec()));
+    let mut security_kotlin = builtin_recipe("security-kotlin", "Basic SecurityFilterChain", &["security"], &["kotlin"]);
+    security_kotlin.files.push(("src/main/kotlin/{{packagePath}}/SecurityConfig.kt".to_string(), SECURITY_KOTLIN.as_bytes().to_vec()));
+
+    vec![flyway, postgres, security_java, security_kotlin]
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<(String, Vec<u8>)>) -> anyhow::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            files.push((relative.to_string_lossy().replace('\\', "/"), fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// Load the recipe in `dir`
+pub fn load(dir: &Path) -> anyhow::Result<Recipe> {
+    let manifest_path = dir.join(RECIPE_MANIFEST);
+    let manifest: Manifest = toml::from_str(&fs::read_to_string(&manifest_path)?)
+        .map_err(|e| anyhow::anyhow!("Invalid {}: {}", manifest_path.display(), e))?;
+    if manifest.when.is_empty() {
+        anyhow::bail!("{} needs at least one dependency id in `when`", manifest_path.display());
+    }
+
+    let mut files = Vec::new();
+    if dir.join("files").is_dir() {
+        collect_files(&dir.join("files"), &dir.join("files"), &mut files)?;
+        files.sort();
+    }
+    Ok(Recipe {
+        name: dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        description: manifest.description,
+        when: manifest.when,
+        language: manifest.language,
+        properties: manifest.properties,
+        files,
+        source: Some(dir.to_path_buf()),
+    })
+}
+
+/// Built-in recipes followed by the user's, which replace built-ins of the same name
+pub fn all() -> anyhow::Result<Vec<Recipe>> {
+    let mut recipes = builtin();
+    let dirs = std::iter::once(get_application_config_dir().join("recipes"))
+        .chain(RECIPE_DIRS.get().cloned().unwrap_or_default());
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(&dir) else { continue };
+        let mut recipe_dirs: Vec<PathBuf> =
+            entries.flatten().map(|e| e.path()).filter(|p| p.join(RECIPE_MANIFEST).is_file()).collect();
+        recipe_dirs.sort();
+        for recipe_dir in recipe_dirs {
+            let recipe = load(&recipe_dir)?;
+            recipes.retain(|r| r.name != recipe.name);
+            recipes.push(recipe);
+        }
+    }
+    Ok(recipes)
+}
+
+/// Apply the properties of a recipe to the project's application config, keeping existing keys
+fn apply_properties(project: &Path, config: &SprintInitConfig, properties: &[(String, String)]) -> anyhow::Result<()> {
+    properties::apply(project, config, None, properties, false)
+}
+
+/// Apply every recipe triggered by `config` to the project in `project`, returning their names
+pub fn apply_all(project: &Path, config: &SprintInitConfig) -> anyhow::Result<Vec<String>> {
+    let variables = overlay::variables(config);
+    let mut applied = Vec::new();
+    for recipe in all()?.into_iter().filter(|r| r.applies_to(config)) {
+        for (file, content) in &recipe.files {
+            let destination = project.join(overlay::render(file, &variables));
+            if destination.exists() {
+                continue;
+            }
+            let content = match String::from_utf8(content.clone()) {
+                Ok(text) => overlay::render(&text, &variables).into_bytes(),
+                Err(e) => e.into_bytes(),
+            };
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&destination, content)?;
+        }
+        if !recipe.properties.is_empty() {
+            let properties: Vec<(String, String)> = recipe
+                .properties
+                .iter()
+                .map(|(k, v)| (k.clone(), overlay::render(v, &variables)))
+                .collect();
+            apply_properties(project, config, &properties)?;
+        }
+        applied.push(recipe.name);
+    }
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_recipes_follow_dependencies_and_language() {
+        let config = SprintInitConfig { dependencies: "web,data-jpa,postgresql,security".to_string(), ..Default::default() };
+        let names: Vec<String> = builtin().into_iter().filter(|r| r.applies_to(&config)).map(|r| r.name).collect();
+        assert_eq!(names, vec!["jpa-postgresql", "security-java"]);
+
+        let kotlin = SprintInitConfig { language: "kotlin".to_string(), dependencies: "security".to_string(), ..Default::default() };
+        let names: Vec<String> = builtin().into_iter().filter(|r| r.applies_to(&kotlin)).map(|r| r.name).collect();
+        assert_eq!(names, vec!["security-kotlin"]);
+    }
+}
@@ -0,0 +1,119 @@
+// share links as produced by the start.spring.io web ui:
+// https://start.spring.io/#!type=maven-project&language=java&platformVersion=3.5.0&...&dependencies=web,lombok
+use crate::types::generic::SprintInitConfig;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// Escaping of the web ui links: encodeURIComponent, except commas stay so the dependency list reads `web,lombok`
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')')
+    .remove(b',');
+
+/// Build the web ui share link for `config` on the Initializr at `base_url`
+pub fn share_link(config: &SprintInitConfig, base_url: &str) -> String {
+    let java_version = config.java_version.to_string();
+    let params = [
+        ("type", config.project_type.as_str()),
+        ("language", config.language.as_str()),
+        ("platformVersion", config.boot_version.as_str()),
+        ("packaging", config.packaging.as_str()),
+        ("configurationFileFormat", config.configuration_file_format.as_str()),
+        ("jvmVersion", java_version.as_str()),
+        ("groupId", config.group_id.as_str()),
+        ("artifactId", config.artifact_id.as_str()),
+        ("name", config.name.as_str()),
+        ("description", config.description.as_str()),
+        ("packageName", config.package_name.as_str()),
+        ("dependencies", config.dependencies.as_str()),
+    ];
+
+    let query = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, utf8_percent_encode(v, COMPONENT)))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!("{}/#!{}", base_url.trim_end_matches('/'), query)
+}
+
+pub fn is_share_link(value: &str) -> bool {
+    value.contains("#!")
+}
+
+/// Parse a web ui share link (or just its `#!` fragment) into a config.
+/// Keys missing from the link keep their defaults.
+pub fn parse_share_link(link: &str) -> anyhow::Result<SprintInitConfig> {
+    let fragment = link.split_once("#!").map(|(_, f)| f).unwrap_or(link);
+    let mut config = SprintInitConfig::default();
+
+    for pair in fragment.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = percent_decode_str(&value.replace('+', " ")).decode_utf8()?.into_owned();
+
+        match key {
+            "type" => config.project_type = value,
+            "language" => config.language = value,
+            "platformVersion" | "bootVersion" => config.boot_version = value,
+            "packaging" => config.packaging = value,
+            "configurationFileFormat" => config.configuration_file_format = value,
+            "jvmVersion" | "javaVersion" => {
+                config.java_version = value
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("Invalid java version in share link: {}", value))?
+            }
+            "groupId" => config.group_id = value,
+            "artifactId" => config.artifact_id = value,
+            "name" => config.name = value,
+            "description" => config.description = value,
+            "packageName" => config.package_name = value,
+            "dependencies" => config.dependencies = value,
+            _ => {}
+        }
+    }
+
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_web_ui_link() {
+        let link = "https://start.spring.io/#!type=gradle-project-kotlin&language=kotlin&platformVersion=3.5.10\
+                    &packaging=war&jvmVersion=21&groupId=org.turntabl&artifactId=snake&name=snake\
+                    &description=Demo%20Snake%20app&packageName=org.turntabl.snake&dependencies=web,lombok";
+
+        let config = parse_share_link(link).unwrap();
+
+        assert_eq!(config.project_type, "gradle-project-kotlin");
+        assert_eq!(config.boot_version, "3.5.10");
+        assert_eq!(config.java_version, 21);
+        assert_eq!(config.description, "Demo Snake app");
+        assert_eq!(config.dependencies, "web,lombok");
+        assert_eq!(config.configuration_file_format, "properties");
+    }
+
+    #[test]
+    fn share_link_round_trips() {
+        let config = SprintInitConfig {
+            description: "Demo & friends".to_string(),
+            dependencies: "security,web".to_string(),
+            ..Default::default()
+        };
+
+        let link = share_link(&config, "https://start.spring.io/");
+
+        assert!(link.starts_with("https://start.spring.io/#!type=maven-project&"));
+        assert!(link.contains("description=Demo%20%26%20friends"));
+        assert!(link.contains("dependencies=security,web"));
+        assert_eq!(parse_share_link(&link).unwrap(), config);
+    }
+}
@@ -0,0 +1,457 @@
+// multi-module workspaces: every module is generated into its own subdirectory, then an
+// aggregator pom.xml / settings.gradle ties them together. the boot parent (maven) or the
+// plugin versions (gradle) move to the root build when all modules agree on them
+use super::xml;
+use crate::api;
+use crate::generator::batch::{self, BatchItem, BatchResult};
+use crate::project::BuildKind;
+use crate::config::migrate;
+use crate::types::config::ConfigMetadata;
+use crate::types::generic::SprintInitConfig;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Coordinates of the aggregator, from the manifest's optional `workspace` table
+#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WorkspaceSettings {
+    pub group_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub version: Option<String>,
+    pub name: Option<String>,
+}
+
+pub struct Workspace {
+    pub root: PathBuf,
+    pub kind: BuildKind,
+    pub results: Vec<BatchResult>,
+    /// whether the boot parent or plugin versions moved to the root build
+    pub hoisted: bool,
+}
+
+const MAVEN_WRAPPER: &[&str] = &["mvnw", "mvnw.cmd", ".mvn"];
+const GRADLE_WRAPPER: &[&str] = &["gradlew", "gradlew.bat", "gradle"];
+const BOOT_PARENT: &str = "spring-boot-starter-parent";
+
+pub fn read_settings(path: &Path) -> anyhow::Result<WorkspaceSettings> {
+    if path.is_dir() {
+        return Ok(WorkspaceSettings::default());
+    }
+    let content = fs::read_to_string(path)?;
+    let metadata = ConfigMetadata::detect(&path.to_string_lossy(), &content, None);
+    match migrate::parse_value(&content, &metadata.file_type)? {
+        Value::Object(mut manifest) => match manifest.remove("workspace") {
+            Some(settings) => Ok(serde_json::from_value(settings)?),
+            None => Ok(WorkspaceSettings::default()),
+        },
+        _ => Ok(WorkspaceSettings::default()),
+    }
+}
+
+fn build_kind(project_type: &str) -> anyhow::Result<BuildKind> {
+    match project_type {
+        "maven-project" => Ok(BuildKind::Maven),
+        "gradle-project" => Ok(BuildKind::GradleGroovy),
+        "gradle-project-kotlin" => Ok(BuildKind::GradleKotlin),
+        other => anyhow::bail!("Workspaces need maven or gradle projects, got '{}'", other),
+    }
+}
+
+/// Build system shared by every module
+fn shared_kind(items: &[BatchItem]) -> anyhow::Result<BuildKind> {
+    let kind = build_kind(&items[0].config.project_type)?;
+    for item in &items[1..] {
+        if build_kind(&item.config.project_type)? != kind {
+            anyhow::bail!(
+                "{} is a {} but {} is a {}, modules must share one build system",
+                items[0].label,
+                items[0].config.project_type,
+                item.label,
+                item.config.project_type
+            );
+        }
+    }
+    Ok(kind)
+}
+
+/// Aggregator pom listing `modules`, inheriting the boot parent when `boot_version` is given
+pub fn aggregator_pom(settings: &WorkspaceSettings, modules: &[String], boot_version: Option<&str>) -> String {
+    let mut pom = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <project xmlns=\"http://maven.apache.org/POM/4.0.0\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\n\
+         \txsi:schemaLocation=\"http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd\">\n\
+         \t<modelVersion>4.0.0</modelVersion>\n",
+    );
+    if let Some(boot_version) = boot_version {
+        pom.push_str(&format!(
+            "\t<parent>\n\t\t<groupId>org.springframework.boot</groupId>\n\t\t<artifactId>{}</artifactId>\n\
+             \t\t<version>{}</version>\n\t\t<relativePath/> <!-- lookup parent from repository -->\n\t</parent>\n",
+            BOOT_PARENT, xml(boot_version)
+        ));
+    }
+    pom.push_str(&format!(
+        "\t<groupId>{}</groupId>\n\t<artifactId>{}</artifactId>\n\t<version>{}</version>\n\t<packaging>pom</packaging>\n",
+        xml(settings.group_id.as_deref().unwrap_or_default()),
+        xml(settings.artifact_id.as_deref().unwrap_or_default()),
+        xml(settings.version.as_deref().unwrap_or_default()),
+    ));
+    if let Some(name) = &settings.name {
+        pom.push_str(&format!("\t<name>{}</name>\n", xml(name)));
+    }
+    pom.push_str("\t<modules>\n");
+    for module in modules {
+        pom.push_str(&format!("\t\t<module>{}</module>\n", xml(module)));
+    }
+    pom.push_str("\t</modules>\n</project>\n");
+    pom
+}
+
+/// Point a module pom's `<parent>` at the aggregator instead of the boot parent
+pub fn reparent_pom(pom: &str, settings: &WorkspaceSettings) -> Option<String> {
+    let start = pom.find("<parent>")?;
+    let end = pom[start..].find("</parent>")? + start + "</parent>".len();
+    if !pom[start..end].contains(BOOT_PARENT) {
+        return None;
+    }
+    let indent: String = pom[..start].rsplit('\n').next().unwrap_or_default().to_string();
+    let inner = format!("{}{}", indent, if indent.contains('\t') { "\t" } else { "    " });
+
+    let parent = format!(
+        "<parent>\n{i}<groupId>{}</groupId>\n{i}<artifactId>{}</artifactId>\n{i}<version>{}</version>\n\
+         {i}<relativePath>../pom.xml</relativePath>\n{}</parent>",
+        xml(settings.group_id.as_deref().unwrap_or_default()),
+        xml(settings.artifact_id.as_deref().unwrap_or_default()),
+        xml(settings.version.as_deref().unwrap_or_default()),
+        indent,
+        i = inner,
+    );
+    Some(format!("{}{}{}", &pom[..start], parent, &pom[end..]))
+}
+
+/// Module pom with the workspace group id and version: dropped when the module inherits them
+/// from the aggregator, set to the workspace values when it keeps the boot parent
+pub fn share_coordinates(pom: &str, settings: &WorkspaceSettings, inherited: bool) -> String {
+    // direct children of <project> sit at the indentation of <modelVersion>
+    let indent = pom
+        .lines()
+        .find(|l| l.trim_start().starts_with("<modelVersion>"))
+        .map(|l| &l[..l.len() - l.trim_start().len()])
+        .unwrap_or("\t");
+    let mut out = Vec::new();
+    for line in pom.lines() {
+        let trimmed = line.trim_start();
+        let own = &line[..line.len() - trimmed.len()] == indent;
+        let shared = [("groupId", &settings.group_id), ("version", &settings.version)]
+            .into_iter()
+            .find(|(tag, _)| own && trimmed.starts_with(&format!("<{}>", tag)));
+        match shared {
+            Some(_) if inherited => {}
+            Some((tag, value)) => out.push(format!("{}<{}>{}</{}>", indent, tag, xml(value.as_deref().unwrap_or_default()), tag)),
+            None => out.push(line.to_string()),
+        }
+    }
+    let mut pom = out.join("\n");
+    pom.push('\n');
+    pom
+}
+
+/// Module build without its own `group`/`version`, which would override the root's `allprojects`
+pub fn strip_coordinates(build: &str) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    for line in build.lines() {
+        let own = ["group", "version"]
+            .iter()
+            .any(|key| line.strip_prefix(key).is_some_and(|rest| rest.trim_start().starts_with('=')));
+        if !own {
+            out.push(line);
+        }
+    }
+    let mut build = out.join("\n");
+    build.push('\n');
+    build
+}
+
+/// `plugins { }` lines carrying a version, e.g. `id 'org.springframework.boot' version '3.5.0'`
+fn versioned_plugins(build: &str) -> Vec<String> {
+    let mut in_plugins = false;
+    let mut plugins = Vec::new();
+    for line in build.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("plugins") && trimmed.ends_with('{') {
+            in_plugins = true;
+        } else if in_plugins && trimmed == "}" {
+            break;
+        } else if in_plugins && trimmed.contains(" version ") {
+            plugins.push(trimmed.to_string());
+        }
+    }
+    plugins
+}
+
+/// Versioned `plugins { }` lines of `build` declaring the plugin `id`
+pub fn plugin_declarations(build: &str, id: &str) -> Vec<String> {
+    versioned_plugins(build)
+        .into_iter()
+        .filter(|p| p.contains(&format!("'{}'", id)) || p.contains(&format!("\"{}\"", id)))
+        .collect()
+}
+
+/// Plugin declarations with the same version in every module build
+pub fn shared_plugins(builds: &[String]) -> Vec<String> {
+    let Some((first, rest)) = builds.split_first() else { return Vec::new() };
+    versioned_plugins(first)
+        .into_iter()
+        .filter(|p| rest.iter().all(|b| versioned_plugins(b).contains(p)))
+        .collect()
+}
+
+/// Drop the versions of `plugins` from a module build; the root build declares them
+pub fn strip_plugin_versions(build: &str, plugins: &[String]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    for line in build.lines() {
+        let trimmed = line.trim();
+        match plugins.iter().find(|p| p.as_str() == trimmed) {
+            Some(plugin) => {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let id = plugin.split(" version ").next().unwrap_or(plugin);
+                out.push(format!("{}{}", indent, id));
+            }
+            None => out.push(line.to_string()),
+        }
+    }
+    let mut build = out.join("\n");
+    build.push('\n');
+    build
+}
+
+pub fn root_settings_gradle(kind: BuildKind, name: &str, modules: &[String]) -> String {
+    match kind {
+        BuildKind::GradleKotlin => {
+            let quoted: Vec<String> = modules.iter().map(|m| format!("\"{}\"", m)).collect();
+            format!("rootProject.name = \"{}\"\ninclude({})\n", name, quoted.join(", "))
+        }
+        _ => {
+            let quoted: Vec<String> = modules.iter().map(|m| format!("'{}'", m)).collect();
+            format!("rootProject.name = '{}'\ninclude {}\n", name, quoted.join(", "))
+        }
+    }
+}
+
+pub fn root_build_gradle(kind: BuildKind, settings: &WorkspaceSettings, plugins: &[String]) -> String {
+    let quote = if kind == BuildKind::GradleKotlin { '"' } else { '\'' };
+    let mut build = String::new();
+    if !plugins.is_empty() {
+        build.push_str("plugins {\n");
+        for plugin in plugins {
+            build.push_str(&format!("\t{} apply false\n", plugin));
+        }
+        build.push_str("}\n\n");
+    }
+    build.push_str(&format!(
+        "allprojects {{\n\tgroup = {q}{}{q}\n\tversion = {q}{}{q}\n}}\n",
+        settings.group_id.as_deref().unwrap_or_default(),
+        settings.version.as_deref().unwrap_or_default(),
+        q = quote,
+    ));
+    build
+}
+
+/// Keep one copy of the build wrapper, at the root
+fn hoist_wrapper(root: &Path, modules: &[String], kind: BuildKind) -> anyhow::Result<()> {
+    let files = if kind == BuildKind::Maven { MAVEN_WRAPPER } else { GRADLE_WRAPPER };
+    for file in files {
+        for (i, module) in modules.iter().enumerate() {
+            let path = root.join(module).join(file);
+            if !path.exists() {
+                continue;
+            }
+            if i == 0 && !root.join(file).exists() {
+                fs::rename(&path, root.join(file))?;
+            } else if path.is_dir() {
+                fs::remove_dir_all(&path)?;
+            } else {
+                fs::remove_file(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generate every project in the manifest or directory at `path` as a module under `dir/<artifactId>`
+pub async fn generate_workspace(path: &Path, dir: &Path, jobs: usize) -> anyhow::Result<Workspace> {
+    let mut items = batch::load(path)?;
+    let kind = shared_kind(&items)?;
+    // the aggregator needs the version the modules get, not an empty one
+    if items.iter().any(|i| i.config.boot_version.is_empty()) {
+        let capabilities = api::get_capabilities().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+        let boot_version = SprintInitConfig::from_capabilities(&capabilities).boot_version;
+        for item in items.iter_mut().filter(|i| i.config.boot_version.is_empty()) {
+            item.config.boot_version = boot_version.clone();
+        }
+    }
+
+    let mut settings = read_settings(path)?;
+    let first = &items[0].config;
+    settings.group_id.get_or_insert_with(|| first.group_id.clone());
+    settings.version.get_or_insert_with(|| first.version.clone());
+    settings.artifact_id.get_or_insert_with(|| {
+        path.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "workspace".to_string())
+    });
+    let boot_version = first.boot_version.clone();
+    let same_boot = items.iter().all(|i| i.config.boot_version == boot_version);
+    let modules: Vec<String> = items.iter().map(|i| i.config.artifact_id.clone()).collect();
+
+    let root = dir.join(settings.artifact_id.as_deref().unwrap_or_default());
+    let results = batch::generate_all(items, true, &root, jobs).await?;
+    if results.iter().any(|r| r.result.is_err()) {
+        return Ok(Workspace { root, kind, results, hoisted: false });
+    }
+    for module in &modules {
+        let archive = root.join(module).join(format!("{}.zip", module));
+        if archive.exists() {
+            fs::remove_file(archive)?;
+        }
+    }
+
+    let hoisted = match kind {
+        BuildKind::Maven => {
+            for module in &modules {
+                let pom_path = root.join(module).join("pom.xml");
+                let pom = fs::read_to_string(&pom_path)?;
+                let pom = match reparent_pom(&pom, &settings).filter(|_| same_boot) {
+                    Some(pom) => share_coordinates(&pom, &settings, true),
+                    None => share_coordinates(&pom, &settings, false),
+                };
+                fs::write(&pom_path, pom)?;
+            }
+            fs::write(root.join("pom.xml"), aggregator_pom(&settings, &modules, same_boot.then_some(boot_version.as_str())))?;
+            same_boot
+        }
+        BuildKind::GradleGroovy | BuildKind::GradleKotlin => {
+            let build_paths: Vec<PathBuf> = modules.iter().map(|m| root.join(m).join(kind.file_name())).collect();
+            let builds = build_paths.iter().map(fs::read_to_string).collect::<Result<Vec<_>, _>>()?;
+            let plugins = shared_plugins(&builds);
+            for (path, build) in build_paths.iter().zip(&builds) {
+                fs::write(path, strip_coordinates(&strip_plugin_versions(build, &plugins)))?;
+            }
+            for module in &modules {
+                for settings_file in ["settings.gradle", "settings.gradle.kts"] {
+                    let path = root.join(module).join(settings_file);
+                    if path.exists() {
+                        fs::remove_file(path)?;
+                    }
+                }
+            }
+
+            let settings_file = if kind == BuildKind::GradleKotlin { "settings.gradle.kts" } else { "settings.gradle" };
+            let name = settings.artifact_id.clone().unwrap_or_default();
+            fs::write(root.join(settings_file), root_settings_gradle(kind, &name, &modules))?;
+            fs::write(root.join(kind.file_name()), root_build_gradle(kind, &settings, &plugins))?;
+            !plugins.is_empty()
+        }
+    };
+    hoist_wrapper(&root, &modules, kind)?;
+
+    Ok(Workspace { root, kind, results, hoisted })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> WorkspaceSettings {
+        WorkspaceSettings {
+            group_id: Some("com.acme".to_string()),
+            artifact_id: Some("platform".to_string()),
+            version: Some("1.0.0".to_string()),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn module_poms_inherit_from_the_aggregator() {
+        let pom = "<project>\n\t<parent>\n\t\t<groupId>org.springframework.boot</groupId>\n\
+                   \t\t<artifactId>spring-boot-starter-parent</artifactId>\n\t\t<version>3.5.0</version>\n\
+                   \t\t<relativePath/>\n\t</parent>\n\t<artifactId>orders</artifactId>\n</project>\n";
+
+        let reparented = reparent_pom(pom, &settings()).unwrap();
+
+        assert!(reparented.contains("\t<parent>\n\t\t<groupId>com.acme</groupId>\n\t\t<artifactId>platform</artifactId>"));
+        assert!(reparented.contains("<relativePath>../pom.xml</relativePath>"));
+        assert!(!reparented.contains(BOOT_PARENT));
+
+        let aggregator = aggregator_pom(&settings(), &["orders".to_string()], Some("3.5.0"));
+        assert!(aggregator.contains("<artifactId>spring-boot-starter-parent</artifactId>"));
+        assert!(aggregator.contains("<module>orders</module>"));
+    }
+
+    #[test]
+    fn modules_take_the_workspace_coordinates() {
+        let module = |group: &str, artifact: &str| {
+            format!(
+                "<project>\n\t<modelVersion>4.0.0</modelVersion>\n\t<parent>\n\t\t<groupId>org.springframework.boot</groupId>\n\
+                 \t\t<artifactId>spring-boot-starter-parent</artifactId>\n\t\t<version>3.5.0</version>\n\t</parent>\n\
+                 \t<groupId>{}</groupId>\n\t<artifactId>{}</artifactId>\n\t<version>0.0.1-SNAPSHOT</version>\n\
+                 \t<dependencies>\n\t\t<dependency>\n\t\t\t<groupId>org.springframework.boot</groupId>\n\t\t</dependency>\n\
+                 \t</dependencies>\n</project>\n",
+                group, artifact
+            )
+        };
+        for (group, artifact) in [("com.orders", "orders"), ("com.billing", "billing")] {
+            let pom = module(group, artifact);
+
+            let inherited = share_coordinates(&reparent_pom(&pom, &settings()).unwrap(), &settings(), true);
+            assert!(!inherited.contains(group) && !inherited.contains("0.0.1-SNAPSHOT"), "{}", inherited);
+            assert!(inherited.contains("\t\t<groupId>com.acme</groupId>\n"));
+            assert!(inherited.contains("\t\t\t<groupId>org.springframework.boot</groupId>\n"));
+
+            let own = share_coordinates(&pom, &settings(), false);
+            assert!(own.contains("\t<groupId>com.acme</groupId>\n\t<artifactId>"));
+            assert!(own.contains("\t<version>1.0.0</version>\n"));
+            assert!(own.contains("<version>3.5.0</version>"));
+        }
+
+        for (group, kind) in [("com.orders", BuildKind::GradleGroovy), ("com.billing", BuildKind::GradleKotlin)] {
+            let build = format!("plugins {{\n\tid 'java'\n}}\n\ngroup = '{}'\nversion = '0.0.1-SNAPSHOT'\n\njava {{\n}}\n", group);
+
+            let stripped = strip_coordinates(&build);
+
+            assert_eq!(stripped, "plugins {\n\tid 'java'\n}\n\n\njava {\n}\n");
+            assert!(root_build_gradle(kind, &settings(), &[]).contains("com.acme"));
+        }
+    }
+
+    #[test]
+    fn aggregator_pom_escapes_its_values() {
+        let settings = WorkspaceSettings { name: Some("Orders & Billing".to_string()), ..settings() };
+
+        let aggregator = aggregator_pom(&settings, &["orders".to_string()], None);
+
+        assert!(aggregator.contains("\t<name>Orders &amp; Billing</name>\n"));
+        roxmltree::Document::parse(&aggregator).unwrap();
+    }
+
+    #[test]
+    fn shared_gradle_plugins_move_to_the_root() {
+        let orders = "plugins {\n\tid 'java'\n\tid 'org.springframework.boot' version '3.5.0'\n\
+                      \tid 'io.spring.dependency-management' version '1.1.7'\n}\n";
+        let billing = "plugins {\n\tid 'java'\n\tid 'org.springframework.boot' version '3.5.0'\n\
+                       \tid 'io.spring.dependency-management' version '1.1.6'\n}\n";
+        let builds = vec![orders.to_string(), billing.to_string()];
+
+        let plugins = shared_plugins(&builds);
+
+        assert_eq!(plugins, vec!["id 'org.springframework.boot' version '3.5.0'".to_string()]);
+        assert!(strip_plugin_versions(orders, &plugins).contains("\tid 'org.springframework.boot'\n"));
+        assert!(root_build_gradle(BuildKind::GradleGroovy, &settings(), &plugins)
+            .contains("\tid 'org.springframework.boot' version '3.5.0' apply false\n"));
+        assert_eq!(
+            root_settings_gradle(BuildKind::GradleKotlin, "platform", &["orders".to_string(), "billing".to_string()]),
+            "rootProject.name = \"platform\"\ninclude(\"orders\", \"billing\")\n"
+        );
+    }
+}
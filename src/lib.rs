@@ -2,6 +2,7 @@ pub mod api;
 pub mod cli;
 pub mod config;
 pub mod generator;
+pub mod project;
 pub mod types;
 
 #[cfg(feature = "tui")]
@@ -1,25 +1,376 @@
-mod api;
-mod types;
-mod cli;
-mod config;
-mod tui;
-mod generator;
+use spring_tui::{api, cli, config, generator, project, tui, types};
 use std::io::Write;
 
+/// One line per setting: key, effective value and the layer it came from
+fn print_settings(resolved: &config::layers::Resolved, out: &mut dyn Write) -> std::io::Result<()> {
+    for key in types::config::ConfigKey::ALL {
+        match (key.get(&resolved.values), resolved.sources.get(key.name())) {
+            (Some(value), Some(source)) => writeln!(out, "{:<26} {:<30} {}", key.name(), value, source)?,
+            _ => writeln!(out, "{:<26} {:<30} -", key.name(), "(not set)")?,
+        }
+    }
+    Ok(())
+}
+
+/// Warnings of `overrides` that the startup `resolved` did not print already
+fn print_new_warnings(overrides: &config::layers::Resolved, resolved: &config::layers::Resolved) {
+    for warning in overrides.warnings.iter().filter(|w| !resolved.warnings.contains(w)) {
+        eprintln!("\x1b[33m! {}\x1b[0m", warning);
+    }
+}
+
 #[tokio::main]
 #[allow(unused_variables)]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    match cli::parse() {
+    let cli = cli::parse();
+    let flags: types::config::GlobalConfig = cli.as_ref().map(|c| c.defaults.clone()).unwrap_or_default().into();
+    let resolved = config::layers::resolve(&std::env::current_dir()?, flags.clone())?;
+    for warning in &resolved.warnings {
+        eprintln!("\x1b[33m! {}\x1b[0m", warning);
+    }
+    if let Some(url) = &resolved.values.base_url {
+        api::util::set_base_url(url);
+    }
+    if let Some(dirs) = &resolved.values.recipe_dirs {
+        generator::recipe::set_recipe_dirs(dirs);
+    }
+    generator::provenance::set_enabled(resolved.values.provenance.unwrap_or(true));
+    match resolved.values.backend.as_deref().map(str::parse::<api::snapshot::Backend>) {
+        Some(Ok(backend)) => api::snapshot::set_backend(backend),
+        Some(Err(e)) => eprintln!("\x1b[33m! {}, using initializr\x1b[0m", e),
+        None => {}
+    }
+    if let Some(dir) = &resolved.values.snapshot_dir {
+        api::snapshot::set_snapshot_dir(dir);
+    }
+    if cli.as_ref().is_some_and(|c| c.verbose) {
+        print_settings(&resolved, &mut std::io::stderr())?;
+    }
+
+    match cli {
         None => {
             // Run TUI
-            tui::run().await?;
+            tui::run(&resolved.values).await?;
         }
+        Some(cli::CliResult { command: Some(command), format, .. }) => match command {
+            cli::Command::Import { path, output } => {
+                let (config, warning) = if generator::share::is_share_link(&path) {
+                    (generator::share::parse_share_link(&path)?, None)
+                } else if generator::command::is_spring_init(&path) {
+                    (generator::command::parse_spring_init(&path)?, None)
+                } else {
+                    let dependencies = api::get_dependencies().await?;
+                    let imported = project::import_project(std::path::Path::new(&path), &dependencies)?;
+                    let warning = (!imported.unmapped.is_empty()).then(|| {
+                        format!("No Initializr dependency matches: {}", imported.unmapped.join(", "))
+                    });
+                    (imported.config, warning)
+                };
+
+                match output {
+                    Some(output) => {
+                        if let Some(warning) = &warning {
+                            println!("\x1b[33m! {}\x1b[0m", warning);
+                        }
+                        let metadata = types::config::ConfigMetadata::from_path(output.clone())?;
+                        generator::generate_project_config_file(&config, metadata.file_type, Some(output.clone()))?;
+                        println!("\x1b[32m✓ Config written to {}\x1b[0m", output);
+                    }
+                    None => tui::run_with_config(config, warning, generator::hooks::HookCommands::from_settings(&resolved.values)).await?,
+                }
+            }
+            cli::Command::Export { file, to, output } => {
+                use generator::command::CommandStyle;
+                use types::config::FileType;
+
+                let config = config::load_config(&file, format)?;
+                let content = match to {
+                    cli::ExportTarget::Yaml => generator::render_config(&config, &FileType::Yaml)?,
+                    cli::ExportTarget::Json => generator::render_config(&config, &FileType::Json)?,
+                    cli::ExportTarget::Toml => generator::render_config(&config, &FileType::Toml)?,
+                    cli::ExportTarget::Curl => generator::command::render_command(&config, CommandStyle::Curl, &api::util::get_base_url()),
+                    cli::ExportTarget::Httpie => generator::command::render_command(&config, CommandStyle::Httpie, &api::util::get_base_url()),
+                    cli::ExportTarget::SpringInit => generator::command::render_command(&config, CommandStyle::SpringInit, &api::util::get_base_url()),
+                };
+
+                match output {
+                    Some(output) => {
+                        std::fs::write(&output, format!("{}\n", content.trim_end()))?;
+                        println!("\x1b[32m✓ Exported to {}\x1b[0m", output);
+                    }
+                    None => println!("{}", content.trim_end()),
+                }
+            }
+            cli::Command::Config { action } => match action {
+                cli::ConfigAction::Explain => {
+                    println!("Layers (lowest precedence first):");
+                    for layer in &resolved.layers {
+                        println!("  {}", layer);
+                    }
+                    println!();
+                    print_settings(&resolved, &mut std::io::stdout())?;
+                }
+                cli::ConfigAction::Get { key } => match config::global_config::get_value(key)? {
+                    Some(value) => println!("{}", value),
+                    None => std::process::exit(1),
+                },
+                cli::ConfigAction::Set { key, value } => {
+                    let path = config::global_config::set_value(key, &value)?;
+                    println!("\x1b[32m✓ {} set in {}\x1b[0m", key.name(), path.display());
+                }
+                cli::ConfigAction::Unset { key } => {
+                    let path = config::global_config::unset_value(key)?;
+                    println!("\x1b[32m✓ {} removed from {}\x1b[0m", key.name(), path.display());
+                }
+                cli::ConfigAction::List => {
+                    match config::util::get_application_config_path() {
+                        Some(path) => println!("# {}", path),
+                        None => println!("# no global config yet, `config set` creates {}", config::util::get_writable_config_path().display()),
+                    }
+                    let global = config::global_config::get_config()?;
+                    for key in types::config::ConfigKey::ALL {
+                        if let Some(value) = key.get(&global) {
+                            println!("{}={}", key.name(), value);
+                        }
+                    }
+                }
+                cli::ConfigAction::Convert { input, output, fill_defaults } => {
+                    let content = config::util::read_source(&input)?;
+                    let from = types::config::ConfigMetadata::detect(&input, &content, format).file_type;
+                    let to = types::config::ConfigMetadata::from_path(output.clone())?.file_type;
+                    let defaults = if fill_defaults {
+                        Some(types::generic::SprintInitConfig::from_capabilities(&api::get_capabilities().await?))
+                    } else {
+                        None
+                    };
+
+                    let converted = config::convert::convert(&content, from, to, defaults.as_ref())?;
+                    std::fs::write(&output, converted)?;
+                    println!("\x1b[32m✓ {} converted to {}\x1b[0m", input, output);
+                }
+                cli::ConfigAction::Migrate { files } => {
+                    let mut failed = Vec::new();
+                    for file in &files {
+                        match config::migrate::migrate_file(file) {
+                            Ok(Some(from)) => {
+                                println!(
+                                    "\x1b[32m✓ {} migrated from v{} to v{}\x1b[0m",
+                                    file,
+                                    from,
+                                    types::generic::CONFIG_VERSION
+                                );
+                                for step in config::migrate::steps_since(from) {
+                                    println!("  - {}", step.description);
+                                }
+                            }
+                            Ok(None) => println!("{} is already up to date", file),
+                            Err(e) => {
+                                println!("\x1b[31m✗ Error migrating {}: {}\x1b[0m", file, e);
+                                failed.push(file.as_str());
+                            }
+                        }
+                    }
+                    if !failed.is_empty() {
+                        return Err(format!("{} of {} configs could not be migrated: {}", failed.len(), files.len(), failed.join(", ")).into());
+                    }
+                }
+            },
+            cli::Command::Batch { path, jobs, extract } => {
+                let items = generator::batch::load(std::path::Path::new(&path))?;
+                let dir = resolved.values.dir.clone().unwrap_or_else(|| ".".to_string());
+                println!("Generating {} projects, {} at a time...", items.len(), jobs);
+
+                let results = generator::batch::generate_all(items, extract, std::path::Path::new(&dir), jobs).await?;
+                print!("{}", generator::batch::summary(&results));
+                if results.iter().any(|r| r.result.is_err()) {
+                    std::process::exit(1);
+                }
+            }
+            cli::Command::AddModule { config: file, repo, module, no_inherit } => {
+                let module_config = match file {
+                    Some(file) => {
+                        let mut module_config = config::load_config(&file, format)?;
+                        let overrides = config::layers::overrides(flags)?;
+                        print_new_warnings(&overrides, &resolved);
+                        overrides.values.apply_to(&mut module_config);
+                        module_config
+                    }
+                    None => {
+                        let mut module_config = types::generic::SprintInitConfig::default();
+                        resolved.values.apply_to(&mut module_config);
+                        module_config
+                    }
+                };
+
+                let added =
+                    project::module::add_module(
+                    std::path::Path::new(&repo),
+                    module_config,
+                    module,
+                    !no_inherit,
+                    generator::hooks::HookCommands::from_settings(&resolved.values).as_ref(),
+                )
+                .await?;
+                println!("\x1b[32m✓ Module generated in {}\x1b[0m", added.dir.display());
+                println!("  registered in {}", added.registry.display());
+                if !added.inherited.is_empty() {
+                    println!("  inherited from the parent build: {}", added.inherited.join(", "));
+                }
+            }
+            cli::Command::AddDeps { ids, project: path, yes } => {
+                let update = project::plan_dependencies(std::path::Path::new(&path), &ids).await?;
+                let name = update.path.display().to_string();
+                let diff = project::diff::unified(&update.current, &update.updated, &name, &name);
+                if diff.is_empty() {
+                    println!("{} already has everything {} needs", name, ids.join(", "));
+                    return Ok(());
+                }
+                print!("{}", project::diff::colorize(&diff));
+
+                if !yes {
+                    print!("Write these changes to {}? [y/N] ", name);
+                    std::io::stdout().flush()?;
+                    let mut answer = String::new();
+                    std::io::stdin().read_line(&mut answer)?;
+                    if !matches!(answer.trim(), "y" | "Y" | "yes") {
+                        println!("Nothing written");
+                        return Ok(());
+                    }
+                }
+                std::fs::write(&update.path, &update.updated)?;
+                println!("\x1b[32m✓ {} updated\x1b[0m", name);
+            }
+            cli::Command::Upgrade { to, from, project: path, apply, review } => {
+                println!("Generating the skeleton for both Spring Boot versions...");
+                let plan = project::upgrade::plan(std::path::Path::new(&path), from, &to).await?;
+                let selected = if review {
+                    tui::upgrade::review(&plan)?
+                } else {
+                    print!("{}", project::diff::colorize(&plan.render()));
+                    match apply.as_deref() {
+                        None => None,
+                        Some("all") => Some((0..plan.hunks.len()).collect()),
+                        Some(numbers) => Some(
+                            numbers
+                                .split(',')
+                                .map(|n| n.trim().parse::<usize>().ok().filter(|n| (1..=plan.hunks.len()).contains(n)).map(|n| n - 1))
+                                .collect::<Option<Vec<usize>>>()
+                                .ok_or_else(|| format!("--apply takes `all` or hunk numbers between 1 and {}", plan.hunks.len()))?,
+                        ),
+                    }
+                };
+
+                if plan.hunks.is_empty() {
+                    println!("No skeleton changes between Spring Boot {} and {}", plan.from, plan.to);
+                } else if let Some(selected) = selected {
+                    let (written, rejected) = plan.apply(&selected)?;
+                    for file in written {
+                        println!("\x1b[32m✓ {} updated\x1b[0m", file);
+                    }
+                    for hunk in rejected {
+                        println!("\x1b[31m✗ {} {} no longer matches the project, apply it by hand\x1b[0m", hunk.file, hunk.header);
+                    }
+                }
+            }
+            cli::Command::Recipes => {
+                for recipe in generator::recipe::all()? {
+                    let source = recipe.source.map(|s| s.display().to_string()).unwrap_or_else(|| "built-in".to_string());
+                    let mut trigger = recipe.when.join(" + ");
+                    if !recipe.language.is_empty() {
+                        trigger.push_str(&format!(" ({})", recipe.language.join(", ")));
+                    }
+                    println!("{:<18} {:<36} {:<30} {}", recipe.name, trigger, recipe.description, source);
+                }
+            }
+            cli::Command::Snapshot => {
+                if api::snapshot::backend() == api::snapshot::Backend::Offline {
+                    return Err("A snapshot is taken from Initializr, run it without the offline backend".into());
+                }
+                let capabilities = api::get_capabilities().await?;
+                api::get_dependencies().await?;
+                let boot_versions: Vec<String> = capabilities
+                    .boot_version
+                    .map(|group| group.values.into_iter().map(|v| v.id).collect())
+                    .unwrap_or_default();
+                for boot_version in &boot_versions {
+                    if let Err(e) = api::get_dependencies_for(boot_version).await {
+                        println!("\x1b[33m! Spring Boot {}: {}\x1b[0m", boot_version, e);
+                    }
+                }
+                println!(
+                    "\x1b[32m✓ Snapshot of {} Spring Boot versions saved to {}\x1b[0m",
+                    boot_versions.len(),
+                    api::snapshot::snapshot_dir().display()
+                );
+            }
+            cli::Command::Serve { bind, cache, max_age, cache_only } => {
+                let mirror = api::mirror::Mirror {
+                    upstream: api::util::get_base_url(),
+                    cache_dir: cache
+                        .map(std::path::PathBuf::from)
+                        .unwrap_or_else(|| config::util::get_application_cache_dir().join("mirror")),
+                    max_age: std::time::Duration::from_secs(max_age),
+                    cache_only,
+                    transport: api::transport::transport(),
+                };
+                let listener = tokio::net::TcpListener::bind(&bind).await?;
+                println!(
+                    "Mirroring {} on http://{}/ (cache {})",
+                    mirror.upstream,
+                    listener.local_addr()?,
+                    mirror.cache_dir.display()
+                );
+                api::mirror::serve(listener, std::sync::Arc::new(mirror)).await?;
+            }
+            cli::Command::Regenerate { lock, extract } => {
+                let lock = generator::lock::LockFile::read(std::path::Path::new(&lock))?;
+                let dir = resolved.values.dir.clone().unwrap_or_else(|| ".".to_string());
+                let regenerated = generator::lock::regenerate(&lock, std::path::Path::new(&dir), extract).await?;
+
+                println!("\x1b[32m✓ Regenerated {} from {}\x1b[0m", regenerated.archive.display(), lock.request_url);
+                if !regenerated.checksum_matches {
+                    // Initializr stamps archive entries with the generation time
+                    println!("\x1b[33m! archive checksum differs from the lock, compare the extracted files if it matters\x1b[0m");
+                }
+                for line in regenerated.drift.iter().chain(&regenerated.warnings) {
+                    println!("\x1b[33m! {}\x1b[0m", line);
+                }
+            }
+            cli::Command::Workspace { path, jobs } => {
+                let dir = resolved.values.dir.clone().unwrap_or_else(|| ".".to_string());
+                let workspace =
+                    generator::workspace::generate_workspace(std::path::Path::new(&path), std::path::Path::new(&dir), jobs).await?;
+                print!("{}", generator::batch::summary(&workspace.results));
+                if workspace.results.iter().any(|r| r.result.is_err()) {
+                    std::process::exit(1);
+                }
+                println!("\x1b[32m✓ Workspace written to {}\x1b[0m", workspace.root.display());
+                if workspace.hoisted {
+                    let what = match workspace.kind {
+                        project::BuildKind::Maven => "spring-boot-starter-parent",
+                        _ => "shared plugin versions",
+                    };
+                    println!("  {} declared once in the root build", what);
+                }
+            }
+            cli::Command::Share { file } => {
+                let config = config::load_config(&file, format)?;
+                println!("{}", generator::share::share_link(&config, &api::util::get_base_url()));
+            }
+        },
         Some(result) => {
             // if let Some(dir) = result.dir {
             //     config::modify_global_config(dir.as_str());
             // }
             if let Some(file) = result.file {
-                let file_clone = file.clone();
+                let overrides = config::layers::overrides(flags)?;
+                print_new_warnings(&overrides, &resolved);
+                if result.verbose {
+                    for (key, source) in overrides.sources.iter().filter(|(k, _)| *k != "dir") {
+                        eprintln!("{} from {} overrides {}", key, source, file);
+                    }
+                }
+                let file_clone = if file == config::util::STDIN { "stdin".to_string() } else { file.clone() };
                 let spinner_handle = tokio::spawn(async move {
                     let chars = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
                     let mut i = 0;
@@ -31,7 +382,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 });
 
-                let res = config::create_project_from_config(file.as_str(), result.extract.unwrap_or(false)).await;
+                let dir = resolved.values.dir.clone().unwrap_or_else(|| ".".to_string());
+                let res = config::create_project_from_config_in(
+                    file.as_str(),
+                    result.format,
+                    result.extract.unwrap_or(false),
+                    std::path::Path::new(&dir),
+                    &overrides.values,
+                    generator::git::GitOptions::enabled(&resolved.values).as_ref(),
+                    generator::hooks::HookCommands::from_settings(&resolved.values).as_ref(),
+                )
+                .await;
                 
                 spinner_handle.abort();
                 // Clear the spinner line
@@ -39,11 +400,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 std::io::stdout().flush().unwrap();
 
                 match res {
-                     Ok(_) => { println!("\x1b[32m✓ Project generated successfully!\x1b[0m"); },
+                     Ok(generation) => {
+                         let output = generation.report();
+                         if !output.is_empty() {
+                             println!("{}", output);
+                         }
+                         println!("\x1b[32m✓ Project generated successfully!\x1b[0m");
+                     },
                      Err(e) => {
                          println!("\x1b[31m✗ Error generating project from config: {}\x1b[0m", e);
                      }
                 }
+            } else {
+                tui::run(&resolved.values).await?;
             }
         }
     }
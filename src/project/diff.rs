@@ -0,0 +1,139 @@
+// line based diff (longest common subsequence) rendered as unified diff hunks
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// A run of changes with up to `context` unchanged lines around it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hunk<'a> {
+    /// first line of the hunk in the old text, 0 based
+    pub old_start: usize,
+    pub new_start: usize,
+    pub lines: Vec<Line<'a>>,
+}
+
+impl Hunk<'_> {
+    fn old_len(&self) -> usize {
+        self.lines.iter().filter(|l| !matches!(l, Line::Added(_))).count()
+    }
+
+    fn new_len(&self) -> usize {
+        self.lines.iter().filter(|l| !matches!(l, Line::Removed(_))).count()
+    }
+
+    pub fn header(&self) -> String {
+        format!("@@ -{},{} +{},{} @@", self.old_start + 1, self.old_len(), self.new_start + 1, self.new_len())
+    }
+}
+
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = length of the common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| Line::Removed(l)));
+    lines.extend(new[j..].iter().map(|l| Line::Added(l)));
+    lines
+}
+
+/// Group a diff into hunks with `context` unchanged lines on each side
+pub fn hunks<'a>(lines: &[Line<'a>], context: usize) -> Vec<Hunk<'a>> {
+    let changed: Vec<usize> = (0..lines.len()).filter(|&i| !matches!(lines[i], Line::Same(_))).collect();
+    let mut hunks = Vec::new();
+    let mut k = 0;
+    while k < changed.len() {
+        let start = changed[k].saturating_sub(context);
+        let mut end = changed[k];
+        while k + 1 < changed.len() && changed[k + 1] <= end + 2 * context + 1 {
+            k += 1;
+            end = changed[k];
+        }
+        let end = (end + context + 1).min(lines.len());
+        k += 1;
+
+        let old_start = lines[..start].iter().filter(|l| !matches!(l, Line::Added(_))).count();
+        let new_start = lines[..start].iter().filter(|l| !matches!(l, Line::Removed(_))).count();
+        hunks.push(Hunk { old_start, new_start, lines: lines[start..end].to_vec() });
+    }
+    hunks
+}
+
+/// Unified diff of `old` and `new`, empty when they are equal
+pub fn unified(old: &str, new: &str, old_name: &str, new_name: &str) -> String {
+    let lines = diff_lines(old, new);
+    let hunks = hunks(&lines, 3);
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for hunk in &hunks {
+        let _ = writeln!(out, "{}", hunk.header());
+        for line in &hunk.lines {
+            let _ = match line {
+                Line::Same(l) => writeln!(out, " {}", l),
+                Line::Removed(l) => writeln!(out, "-{}", l),
+                Line::Added(l) => writeln!(out, "+{}", l),
+            };
+        }
+    }
+    out
+}
+
+/// Colour a unified diff for the terminal
+pub fn colorize(diff: &str) -> String {
+    diff.lines()
+        .map(|l| match l.chars().next() {
+            Some('+') if !l.starts_with("+++") => format!("\x1b[32m{}\x1b[0m\n", l),
+            Some('-') if !l.starts_with("---") => format!("\x1b[31m{}\x1b[0m\n", l),
+            Some('@') => format!("\x1b[36m{}\x1b[0m\n", l),
+            _ => format!("{}\n", l),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_unified_hunks() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let new = "a\nb\nC\nd\ne\nf\ng\nh\ni\nj\nk\n";
+
+        let diff = unified(old, new, "old", "new");
+
+        assert_eq!(
+            diff,
+            "--- old\n+++ new\n@@ -1,6 +1,6 @@\n a\n b\n-c\n+C\n d\n e\n f\n@@ -8,3 +8,4 @@\n h\n i\n j\n+k\n"
+        );
+        assert!(unified(old, old, "old", "new").is_empty());
+    }
+}
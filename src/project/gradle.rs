@@ -0,0 +1,171 @@
+use crate::project::BuildModel;
+
+const DEPENDENCY_CONFIGURATIONS: &[&str] = &[
+    "implementation",
+    "api",
+    "compileOnly",
+    "runtimeOnly",
+    "developmentOnly",
+    "annotationProcessor",
+    "kapt",
+    "testImplementation",
+    "testRuntimeOnly",
+];
+
+/// First string literal in `line`, single or double quoted
+fn first_quoted(line: &str) -> Option<&str> {
+    let start = line.find(['\'', '"'])?;
+    let quote = line[start..].chars().next()?;
+    let rest = &line[start + 1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+/// Value of `key = '...'` (Groovy) or `key = "..."` (Kotlin DSL)
+fn assignment<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(key)?.trim_start();
+    let rest = rest.strip_prefix('=')?;
+    first_quoted(rest)
+}
+
+/// Number following `marker`; the old `1.8` / `VERSION_1_8` spelling gives the minor version
+fn number_after(line: &str, marker: &str) -> Option<i32> {
+    let rest = &line[line.find(marker)? + marker.len()..];
+    let rest = rest.trim_start_matches(['(', '\'', '"', ' ', '=']);
+    let digits: String = rest
+        .strip_prefix("1_")
+        .or_else(|| rest.strip_prefix("1."))
+        .unwrap_or(rest)
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+pub fn parse_build(content: &str, settings: Option<&str>) -> BuildModel {
+    let mut model = BuildModel::default();
+
+    for line in content.lines().map(str::trim) {
+        if line.starts_with("//") {
+            continue;
+        }
+
+        if let Some(v) = assignment(line, "group") {
+            model.group_id = Some(v.to_string());
+        } else if let Some(v) = assignment(line, "version") {
+            model.version = Some(v.to_string());
+        } else if let Some(v) = assignment(line, "description") {
+            model.description = Some(v.to_string());
+        }
+
+        if line.contains("org.springframework.boot") && line.contains("version") && !line.contains(':') {
+            // id 'org.springframework.boot' version '3.5.0' / id("org.springframework.boot") version "3.5.0"
+            if let Some(v) = line.split("version").nth(1).and_then(first_quoted) {
+                model.boot_version = Some(v.to_string());
+            }
+        }
+
+        if line.starts_with("kotlin(\"jvm\")") || line.contains("org.jetbrains.kotlin.jvm") {
+            model.language = Some("kotlin".to_string());
+        } else if line == "groovy" || line == "id 'groovy'" || line == "id(\"groovy\")" {
+            model.language = Some("groovy".to_string());
+        } else if line == "war" || line == "id 'war'" || line == "id(\"war\")" {
+            model.packaging = Some("war".to_string());
+        }
+
+        if let Some(v) = number_after(line, "JavaLanguageVersion.of")
+            .or_else(|| number_after(line, "JavaVersion.VERSION_"))
+            .or_else(|| line.starts_with("sourceCompatibility").then(|| number_after(line, "sourceCompatibility")).flatten())
+        {
+            model.java_version = Some(v);
+        }
+
+        let configuration = line.split(['(', ' ', '\'', '"']).next().unwrap_or_default();
+        if DEPENDENCY_CONFIGURATIONS.contains(&configuration)
+            && let Some(coordinates) = first_quoted(line)
+        {
+            let mut parts = coordinates.split(':');
+            if let (Some(g), Some(a)) = (parts.next(), parts.next()) {
+                model.dependencies.push((g.to_string(), a.to_string()));
+            }
+        }
+    }
+
+    model.artifact_id = settings
+        .and_then(|s| s.lines().find_map(|l| assignment(l.trim(), "rootProject.name")))
+        .map(String::from);
+
+    model
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_build;
+
+    #[test]
+    fn reads_groovy_build() {
+        let build = r#"plugins {
+	id 'java'
+	id 'war'
+	id 'org.springframework.boot' version '4.0.2'
+	id 'io.spring.dependency-management' version '1.1.7'
+}
+
+group = 'org.turntabl'
+version = '0.0.1-SNAPSHOT'
+description = 'Demo hopper game built in Spring boot'
+
+java {
+	toolchain {
+		languageVersion = JavaLanguageVersion.of(17)
+	}
+}
+
+dependencies {
+	implementation 'org.springframework.boot:spring-boot-starter-security'
+	testImplementation 'org.springframework.boot:spring-boot-starter-test'
+}
+"#;
+
+        let model = parse_build(build, Some("rootProject.name = 'hopper'\n"));
+
+        assert_eq!(model.group_id.as_deref(), Some("org.turntabl"));
+        assert_eq!(model.artifact_id.as_deref(), Some("hopper"));
+        assert_eq!(model.boot_version.as_deref(), Some("4.0.2"));
+        assert_eq!(model.java_version, Some(17));
+        assert_eq!(model.packaging.as_deref(), Some("war"));
+        assert_eq!(model.dependencies.len(), 2);
+    }
+
+    #[test]
+    fn reads_kotlin_dsl_build() {
+        let build = r#"plugins {
+	kotlin("jvm") version "1.9.25"
+	id("org.springframework.boot") version "3.5.10"
+}
+
+group = "com.example"
+
+dependencies {
+	implementation("org.springframework.boot:spring-boot-starter-web")
+}
+"#;
+
+        let model = parse_build(build, None);
+
+        assert_eq!(model.language.as_deref(), Some("kotlin"));
+        assert_eq!(model.boot_version.as_deref(), Some("3.5.10"));
+        assert_eq!(
+            model.dependencies,
+            vec![("org.springframework.boot".to_string(), "spring-boot-starter-web".to_string())]
+        );
+    }
+
+    #[test]
+    fn reads_java_8_spellings_as_8() {
+        for line in ["sourceCompatibility = '1.8'", "sourceCompatibility = JavaVersion.VERSION_1_8", "sourceCompatibility = 1.8"] {
+            assert_eq!(parse_build(line, None).java_version, Some(8), "{}", line);
+        }
+        assert_eq!(parse_build("sourceCompatibility = '17'", None).java_version, Some(17));
+        assert_eq!(parse_build("sourceCompatibility = JavaVersion.VERSION_11", None).java_version, Some(11));
+    }
+}
@@ -0,0 +1,161 @@
+use crate::project::BuildModel;
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|c| c.is_element() && c.tag_name().name() == name)
+}
+
+fn child_text(node: Node<'_, '_>, name: &str) -> Option<String> {
+    child(node, name)
+        .and_then(|c| c.text())
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+/// Expand `${property}` references using the pom's <properties>
+fn resolve(value: Option<String>, properties: &HashMap<String, String>) -> Option<String> {
+    let value = value?;
+    match value.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+        Some(key) => properties.get(key).cloned(),
+        None => Some(value),
+    }
+}
+
+pub fn parse_pom(content: &str) -> anyhow::Result<BuildModel> {
+    let doc = Document::parse(content)?;
+    let project = doc.root_element();
+
+    let properties: HashMap<String, String> = child(project, "properties")
+        .map(|p| {
+            p.children()
+                .filter(|c| c.is_element())
+                .filter_map(|c| Some((c.tag_name().name().to_string(), c.text()?.trim().to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let parent = child(project, "parent");
+    let parent_is_boot = parent
+        .and_then(|p| child_text(p, "artifactId"))
+        .is_some_and(|a| a == "spring-boot-starter-parent");
+
+    let boot_version = if parent_is_boot {
+        parent.and_then(|p| child_text(p, "version"))
+    } else {
+        properties.get("spring-boot.version").cloned()
+    };
+
+    let java_version = ["java.version", "maven.compiler.release", "maven.compiler.source"]
+        .iter()
+        .find_map(|k| properties.get(*k))
+        .and_then(|v| v.trim_start_matches("1.").parse().ok());
+
+    let plugins: Vec<String> = project
+        .descendants()
+        .filter(|n| n.is_element() && n.tag_name().name() == "plugin")
+        .filter_map(|p| child_text(p, "artifactId"))
+        .collect();
+    let language = if plugins.iter().any(|p| p == "kotlin-maven-plugin") {
+        Some("kotlin".to_string())
+    } else if plugins.iter().any(|p| p == "gmavenplus-plugin") {
+        Some("groovy".to_string())
+    } else {
+        None
+    };
+
+    let dependencies = child(project, "dependencies")
+        .map(|deps| {
+            deps.children()
+                .filter(|d| d.is_element() && d.tag_name().name() == "dependency")
+                .filter_map(|d| Some((child_text(d, "groupId")?, child_text(d, "artifactId")?)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(BuildModel {
+        group_id: child_text(project, "groupId").or_else(|| parent.and_then(|p| child_text(p, "groupId"))),
+        artifact_id: child_text(project, "artifactId"),
+        version: resolve(child_text(project, "version"), &properties),
+        name: child_text(project, "name"),
+        description: child_text(project, "description"),
+        packaging: child_text(project, "packaging").filter(|p| p != "pom"),
+        language,
+        java_version,
+        boot_version,
+        boot_parent: parent_is_boot,
+        dependencies,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_pom;
+
+    #[test]
+    fn reads_generated_pom() {
+        let pom = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+    <modelVersion>4.0.0</modelVersion>
+    <parent>
+        <groupId>org.springframework.boot</groupId>
+        <artifactId>spring-boot-starter-parent</artifactId>
+        <version>3.5.10</version>
+        <relativePath/> <!-- lookup parent from repository -->
+    </parent>
+    <groupId>org.turntabl</groupId>
+    <artifactId>snake</artifactId>
+    <version>0.0.1-SNAPSHOT</version>
+    <name>snakeapp</name>
+    <description>Demo Snake java app</description>
+    <properties>
+        <java.version>21</java.version>
+    </properties>
+    <dependencies>
+        <dependency>
+            <groupId>org.springframework.boot</groupId>
+            <artifactId>spring-boot-starter-web</artifactId>
+        </dependency>
+    </dependencies>
+    <build>
+        <plugins>
+            <plugin>
+                <groupId>org.jetbrains.kotlin</groupId>
+                <artifactId>kotlin-maven-plugin</artifactId>
+            </plugin>
+        </plugins>
+    </build>
+</project>"#;
+
+        let model = parse_pom(pom).unwrap();
+
+        assert_eq!(model.group_id.as_deref(), Some("org.turntabl"));
+        assert_eq!(model.artifact_id.as_deref(), Some("snake"));
+        assert_eq!(model.boot_version.as_deref(), Some("3.5.10"));
+        assert!(model.boot_parent);
+        assert_eq!(model.java_version, Some(21));
+        assert_eq!(model.language.as_deref(), Some("kotlin"));
+        assert_eq!(model.packaging, None);
+        assert_eq!(
+            model.dependencies,
+            vec![("org.springframework.boot".to_string(), "spring-boot-starter-web".to_string())]
+        );
+    }
+
+    #[test]
+    fn reads_the_boot_version_of_a_bom_import() {
+        let pom = r#"<project>
+    <groupId>com.acme</groupId>
+    <artifactId>platform</artifactId>
+    <packaging>pom</packaging>
+    <properties>
+        <spring-boot.version>3.4.2</spring-boot.version>
+    </properties>
+</project>"#;
+
+        let model = parse_pom(pom).unwrap();
+
+        assert_eq!(model.boot_version.as_deref(), Some("3.4.2"));
+        assert!(!model.boot_parent);
+    }
+}
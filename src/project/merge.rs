@@ -0,0 +1,355 @@
+// merges the dependencies, BOMs and repositories of an Initializr generated build file
+// into an existing one, leaving everything else in the existing file untouched
+use roxmltree::{Document, Node};
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|c| c.is_element() && c.tag_name().name() == name)
+}
+
+fn child_text(node: Node, name: &str) -> String {
+    child(node, name).and_then(|c| c.text()).unwrap_or_default().trim().to_string()
+}
+
+fn elements<'a, 'input>(node: Option<Node<'a, 'input>>, name: &'a str) -> Vec<Node<'a, 'input>> {
+    node.map(|n| n.children().filter(|c| c.is_element() && c.tag_name().name() == name).collect())
+        .unwrap_or_default()
+}
+
+/// Identity of a `<dependency>` or `<repository>`
+fn pom_key(node: Node) -> String {
+    match node.tag_name().name() {
+        "dependency" => format!("{}:{}", child_text(node, "groupId"), child_text(node, "artifactId")),
+        _ => child_text(node, "id"),
+    }
+}
+
+fn indent_before(content: &str, pos: usize) -> &str {
+    let line_start = content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let line = &content[line_start..pos];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+struct Insert {
+    pos: usize,
+    text: String,
+}
+
+/// Queue `snippets` for insertion just before the closing tag of `container`
+fn insert_into(existing: &str, container: Node, snippets: &[String], inserts: &mut Vec<Insert>) {
+    if snippets.is_empty() {
+        return;
+    }
+    let end = container.range().end;
+    let close = existing[..end].rfind("</").unwrap_or(end);
+    let line_start = existing[..close].rfind('\n').map(|i| i + 1).unwrap_or(close);
+    let indent = indent_before(existing, close);
+    let mut text = String::new();
+    for snippet in snippets {
+        text.push_str(&format!("{}\t{}\n", indent, snippet));
+    }
+    // closing tag on its own line: insert above it, otherwise right before it
+    if existing[line_start..close].trim().is_empty() {
+        inserts.push(Insert { pos: line_start, text });
+    } else {
+        inserts.push(Insert { pos: close, text: format!("\n{}{}", text, indent) });
+    }
+}
+
+/// Wrap `snippets` in a new `<tag>` block placed before the closing tag of `parent`
+fn insert_block(existing: &str, parent: Node, open: &str, close: &str, snippets: &[String], inserts: &mut Vec<Insert>) {
+    if snippets.is_empty() {
+        return;
+    }
+    let pos = existing[..parent.range().end].rfind("</").unwrap_or(parent.range().end);
+    let line_start = existing[..pos].rfind('\n').map(|i| i + 1).unwrap_or(pos);
+    let indent = format!("{}\t", indent_before(existing, pos));
+    let mut text = format!("{}{}\n", indent, open);
+    for snippet in snippets {
+        text.push_str(&format!("{}\t{}\n", indent, snippet));
+    }
+    text.push_str(&format!("{}{}\n", indent, close));
+    inserts.push(Insert { pos: line_start, text });
+}
+
+/// Source of the elements of `reference` missing from `existing`, matched with `pom_key`
+fn missing(reference_src: &str, reference: &[Node], existing: &[Node]) -> Vec<String> {
+    let known: Vec<String> = existing.iter().map(|n| pom_key(*n)).collect();
+    reference
+        .iter()
+        .filter(|n| !known.contains(&pom_key(**n)))
+        .map(|n| reference_src[n.range()].to_string())
+        .collect()
+}
+
+/// Merge dependencies, BOM imports, version properties and repositories of `reference` into `existing`
+pub fn merge_pom(existing: &str, reference: &str) -> anyhow::Result<String> {
+    let doc = Document::parse(existing)?;
+    let ref_doc = Document::parse(reference)?;
+    let project = doc.root_element();
+    let ref_project = ref_doc.root_element();
+    let mut inserts = Vec::new();
+
+    // properties such as <spring-cloud.version> used by BOMs
+    let properties = child(project, "properties");
+    let known_properties: Vec<&str> = properties
+        .map(|p| p.children().filter(|c| c.is_element()).map(|c| c.tag_name().name()).collect())
+        .unwrap_or_default();
+    let new_properties: Vec<String> = child(ref_project, "properties")
+        .map(|p| {
+            p.children()
+                .filter(|c| c.is_element() && !known_properties.contains(&c.tag_name().name()))
+                .map(|c| reference[c.range()].to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    match properties {
+        Some(node) => insert_into(existing, node, &new_properties, &mut inserts),
+        None => insert_block(existing, project, "<properties>", "</properties>", &new_properties, &mut inserts),
+    }
+
+    let dependencies = child(project, "dependencies");
+    let new_dependencies = missing(
+        reference,
+        &elements(child(ref_project, "dependencies"), "dependency"),
+        &elements(dependencies, "dependency"),
+    );
+    match dependencies {
+        Some(node) => insert_into(existing, node, &new_dependencies, &mut inserts),
+        None => insert_block(existing, project, "<dependencies>", "</dependencies>", &new_dependencies, &mut inserts),
+    }
+
+    let managed = child(project, "dependencyManagement").and_then(|d| child(d, "dependencies"));
+    let new_boms = missing(
+        reference,
+        &elements(child(ref_project, "dependencyManagement").and_then(|d| child(d, "dependencies")), "dependency"),
+        &elements(managed, "dependency"),
+    );
+    match (managed, child(project, "dependencyManagement")) {
+        (Some(node), _) => insert_into(existing, node, &new_boms, &mut inserts),
+        (None, Some(management)) => {
+            insert_block(existing, management, "<dependencies>", "</dependencies>", &new_boms, &mut inserts)
+        }
+        (None, None) if !new_boms.is_empty() => {
+            let mut block = vec!["<dependencies>".to_string()];
+            block.extend(new_boms.iter().map(|b| format!("\t{}", b)));
+            block.push("</dependencies>".to_string());
+            insert_block(existing, project, "<dependencyManagement>", "</dependencyManagement>", &block, &mut inserts);
+        }
+        (None, None) => {}
+    }
+
+    for (list, item) in [("repositories", "repository"), ("pluginRepositories", "pluginRepository")] {
+        let node = child(project, list);
+        let new = missing(reference, &elements(child(ref_project, list), item), &elements(node, item));
+        match node {
+            Some(node) => insert_into(existing, node, &new, &mut inserts),
+            None => insert_block(existing, project, &format!("<{}>", list), &format!("</{}>", list), &new, &mut inserts),
+        }
+    }
+
+    // apply back to front so earlier positions stay valid; equal positions keep their queue order
+    let mut merged = existing.to_string();
+    inserts.sort_by_key(|i| i.pos);
+    for insert in inserts.iter().rev() {
+        merged.insert_str(insert.pos, &insert.text);
+    }
+    Ok(merged)
+}
+
+/// Top level `name { ... }` block: index of the opening and closing line
+fn gradle_block(lines: &[&str], name: &str) -> Option<(usize, usize)> {
+    let mut depth = 0i32;
+    let mut open = None;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if depth == 0 && open.is_none() {
+            let rest = trimmed.strip_prefix(name).map(str::trim_start);
+            if rest.is_some_and(|r| r.starts_with('{')) {
+                open = Some(i);
+            }
+        }
+        depth += trimmed.matches('{').count() as i32 - trimmed.matches('}').count() as i32;
+        if let Some(open) = open
+            && depth == 0
+        {
+            return Some((open, i));
+        }
+    }
+    None
+}
+
+/// `group:artifact` of a dependency line, or the trimmed line for anything else
+fn gradle_key(line: &str) -> String {
+    let trimmed = line.trim();
+    let quoted = trimmed.find(['\'', '"']).and_then(|start| {
+        let quote = trimmed[start..].chars().next()?;
+        let rest = &trimmed[start + 1..];
+        rest.find(quote).map(|end| &rest[..end])
+    });
+    match quoted {
+        Some(coordinates) if coordinates.matches(':').count() >= 1 && !trimmed.starts_with("set(") => {
+            coordinates.split(':').take(2).collect::<Vec<_>>().join(":")
+        }
+        _ => trimmed.to_string(),
+    }
+}
+
+/// Lines of `block` in the reference whose key is not in the same block of the existing build
+fn missing_lines<'a>(existing: &[&str], reference: &[&'a str], block: &str) -> Vec<&'a str> {
+    let Some((ref_open, ref_close)) = gradle_block(reference, block) else { return Vec::new() };
+    let known: Vec<String> = gradle_block(existing, block)
+        .map(|(open, close)| existing[open + 1..close].iter().map(|l| gradle_key(l)).collect())
+        .unwrap_or_default();
+    reference[ref_open + 1..ref_close]
+        .iter()
+        .filter(|l| !l.trim().is_empty() && !l.trim().ends_with('{') && l.trim() != "}")
+        .filter(|l| !known.contains(&gradle_key(l)))
+        .copied()
+        .collect()
+}
+
+/// Merge repositories, dependencies, version variables and BOM imports of `reference` into `existing`
+pub fn merge_gradle(existing: &str, reference: &str) -> String {
+    let mut lines: Vec<String> = existing.lines().map(String::from).collect();
+    let ref_lines: Vec<&str> = reference.lines().collect();
+
+    // version variables: Groovy `ext { set(...) }` or Kotlin `extra["..."] = ...`
+    let view: Vec<&str> = existing.lines().collect();
+    let new_ext = missing_lines(&view, &ref_lines, "ext");
+    let new_extra: Vec<&str> = ref_lines
+        .iter()
+        .filter(|l| l.trim_start().starts_with("extra[") && !view.iter().any(|e| e.trim() == l.trim()))
+        .copied()
+        .collect();
+    let new_repositories = missing_lines(&view, &ref_lines, "repositories");
+    let new_dependencies = missing_lines(&view, &ref_lines, "dependencies");
+    let boms: Vec<&str> = ref_lines.iter().filter(|l| l.contains("mavenBom")).copied().collect();
+    let new_boms: Vec<&str> = boms
+        .into_iter()
+        .filter(|b| !view.iter().any(|e| e.trim() == b.trim()))
+        .collect();
+
+    // insert into existing blocks bottom-up, so indices found earlier stay valid
+    let mut edits: Vec<(usize, Vec<String>)> = Vec::new();
+    let dependencies_block = gradle_block(&view, "dependencies");
+    for (block, new) in [("repositories", &new_repositories), ("dependencies", &new_dependencies)] {
+        if new.is_empty() {
+            continue;
+        }
+        match gradle_block(&view, block) {
+            Some((_, close)) => edits.push((close, new.iter().map(|l| l.to_string()).collect())),
+            None => {
+                let mut text = vec![format!("{} {{", block)];
+                text.extend(new.iter().map(|l| l.to_string()));
+                text.push("}".to_string());
+                text.push(String::new());
+                let at = dependencies_block.map(|(open, _)| open).unwrap_or(lines.len());
+                edits.push((at, text));
+            }
+        }
+    }
+
+    let before_dependencies = dependencies_block.map(|(open, _)| open).unwrap_or(lines.len());
+    if !new_ext.is_empty() {
+        match gradle_block(&view, "ext") {
+            Some((_, close)) => edits.push((close, new_ext.iter().map(|l| l.to_string()).collect())),
+            None => {
+                let mut text = vec!["ext {".to_string()];
+                text.extend(new_ext.iter().map(|l| l.to_string()));
+                text.push("}".to_string());
+                text.push(String::new());
+                edits.push((before_dependencies, text));
+            }
+        }
+    }
+    if !new_extra.is_empty() {
+        let mut text: Vec<String> = new_extra.iter().map(|l| l.to_string()).collect();
+        text.push(String::new());
+        edits.push((before_dependencies, text));
+    }
+
+    if !new_boms.is_empty() {
+        match gradle_block(&view, "dependencyManagement") {
+            Some((open, close)) => {
+                let imports = gradle_block(&view[open + 1..close], "imports").map(|(_, c)| open + 1 + c);
+                match imports {
+                    Some(imports_close) => edits.push((imports_close, new_boms.iter().map(|l| l.to_string()).collect())),
+                    None => {
+                        let mut text = vec!["\timports {".to_string()];
+                        text.extend(new_boms.iter().map(|l| l.to_string()));
+                        text.push("\t}".to_string());
+                        edits.push((close, text));
+                    }
+                }
+            }
+            None => {
+                if lines.last().is_some_and(|l| !l.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                let mut text = vec!["dependencyManagement {".to_string(), "\timports {".to_string()];
+                text.extend(new_boms.iter().map(|l| l.to_string()));
+                text.push("\t}".to_string());
+                text.push("}".to_string());
+                edits.push((lines.len(), text));
+            }
+        }
+    }
+
+    edits.sort_by_key(|(at, _)| *at);
+    for (at, text) in edits.into_iter().rev() {
+        lines.splice(at..at, text);
+    }
+    let mut merged = lines.join("\n");
+    merged.push('\n');
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_pom_dependencies_boms_and_properties() {
+        let existing = "<project>\n\t<properties>\n\t\t<java.version>21</java.version>\n\t</properties>\n\
+                        \t<dependencies>\n\t\t<dependency>\n\t\t\t<groupId>org.springframework.boot</groupId>\n\
+                        \t\t\t<artifactId>spring-boot-starter-web</artifactId>\n\t\t</dependency>\n\t</dependencies>\n</project>\n";
+        let reference = "<project>\n\t<properties>\n\t\t<java.version>21</java.version>\n\
+                         \t\t<spring-cloud.version>2024.0.0</spring-cloud.version>\n\t</properties>\n\
+                         \t<dependencies>\n\t\t<dependency>\n\t\t\t<groupId>org.springframework.boot</groupId>\n\
+                         \t\t\t<artifactId>spring-boot-starter-web</artifactId>\n\t\t</dependency>\n\
+                         \t\t<dependency>\n\t\t\t<groupId>org.springframework.cloud</groupId>\n\
+                         \t\t\t<artifactId>spring-cloud-starter-config</artifactId>\n\t\t</dependency>\n\t</dependencies>\n\
+                         \t<dependencyManagement>\n\t\t<dependencies>\n\t\t\t<dependency>\n\
+                         \t\t\t\t<groupId>org.springframework.cloud</groupId>\n\
+                         \t\t\t\t<artifactId>spring-cloud-dependencies</artifactId>\n\t\t\t</dependency>\n\
+                         \t\t</dependencies>\n\t</dependencyManagement>\n</project>\n";
+
+        let merged = merge_pom(existing, reference).unwrap();
+
+        assert!(merged.contains("\t\t<spring-cloud.version>2024.0.0</spring-cloud.version>\n\t</properties>"));
+        assert_eq!(merged.matches("spring-boot-starter-web").count(), 1);
+        assert!(merged.contains("<artifactId>spring-cloud-starter-config</artifactId>\n\t\t</dependency>\n\t</dependencies>"));
+        assert!(merged.contains("\t<dependencyManagement>\n\t\t<dependencies>\n\t\t\t<dependency>"));
+        assert!(Document::parse(&merged).is_ok());
+        assert_eq!(merge_pom(&merged, reference).unwrap(), merged);
+    }
+
+    #[test]
+    fn merges_gradle_blocks() {
+        let existing = "plugins {\n\tid 'java'\n}\n\nrepositories {\n\tmavenCentral()\n}\n\n\
+                        dependencies {\n\timplementation(\"org.springframework.boot:spring-boot-starter-web\")\n}\n";
+        let reference = "plugins {\n\tid 'java'\n}\n\nrepositories {\n\tmavenCentral()\n}\n\n\
+                         ext {\n\tset('springCloudVersion', \"2024.0.0\")\n}\n\n\
+                         dependencies {\n\timplementation 'org.springframework.boot:spring-boot-starter-web'\n\
+                         \timplementation 'org.springframework.cloud:spring-cloud-starter-config'\n}\n\n\
+                         dependencyManagement {\n\timports {\n\t\tmavenBom \"org.springframework.cloud:spring-cloud-dependencies:${springCloudVersion}\"\n\t}\n}\n";
+
+        let merged = merge_gradle(existing, reference);
+
+        assert_eq!(merged.matches("spring-boot-starter-web").count(), 1);
+        assert!(merged.contains("\timplementation 'org.springframework.cloud:spring-cloud-starter-config'\n}"));
+        assert!(merged.contains("ext {\n\tset('springCloudVersion', \"2024.0.0\")\n}\n\ndependencies {"));
+        assert!(merged.ends_with("dependencyManagement {\n\timports {\n\t\tmavenBom \"org.springframework.cloud:spring-cloud-dependencies:${springCloudVersion}\"\n\t}\n}\n"));
+        assert_eq!(merge_gradle(&merged, reference), merged);
+    }
+}
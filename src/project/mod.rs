@@ -0,0 +1,345 @@
+// reads existing Maven / Gradle builds back into a SprintInitConfig so a new
+// project can be generated "just like this one"
+mod gradle;
+mod maven;
+pub mod diff;
+pub mod merge;
+pub mod module;
+pub mod upgrade;
+
+use crate::types::api::InitializrDependencies;
+use crate::types::generic::{normalize_dependencies, SprintInitConfig, CONFIG_VERSION};
+use crate::{api, generator};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Build flavour detected from the build file name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuildKind {
+    Maven,
+    GradleGroovy,
+    GradleKotlin,
+}
+
+impl BuildKind {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            BuildKind::Maven => "pom.xml",
+            BuildKind::GradleGroovy => "build.gradle",
+            BuildKind::GradleKotlin => "build.gradle.kts",
+        }
+    }
+
+    /// Initializr `type` id producing this kind of build
+    pub fn project_type(&self) -> &'static str {
+        match self {
+            BuildKind::Maven => "maven-project",
+            BuildKind::GradleGroovy => "gradle-project",
+            BuildKind::GradleKotlin => "gradle-project-kotlin",
+        }
+    }
+}
+
+/// Settings recovered from a build file before they are mapped onto Initializr ids
+#[derive(Debug, Default)]
+pub struct BuildModel {
+    pub group_id: Option<String>,
+    pub artifact_id: Option<String>,
+    pub version: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub packaging: Option<String>,
+    pub language: Option<String>,
+    pub java_version: Option<i32>,
+    pub boot_version: Option<String>,
+    /// the pom inherits from spring-boot-starter-parent, rather than naming the version in a property
+    pub boot_parent: bool,
+    /// groupId:artifactId of every declared dependency
+    pub dependencies: Vec<(String, String)>,
+}
+
+pub struct ImportedProject {
+    pub config: SprintInitConfig,
+    /// groupId:artifactId of dependencies with no matching Initializr id
+    pub unmapped: Vec<String>,
+}
+
+/// Artifacts Initializr adds on its own; they never map to a selectable dependency
+const IMPLICIT_ARTIFACTS: &[&str] = &[
+    "spring-boot-starter",
+    "spring-boot-starter-test",
+    "junit-platform-launcher",
+    "kotlin-reflect",
+    "kotlin-stdlib",
+    "kotlin-test-junit5",
+    "jackson-module-kotlin",
+    "reactor-test",
+];
+
+/// Locate the build file for `path`, which may be a project directory or the build file itself
+pub fn find_build_file(path: &Path) -> anyhow::Result<(PathBuf, BuildKind)> {
+    let kinds = [BuildKind::Maven, BuildKind::GradleKotlin, BuildKind::GradleGroovy];
+
+    if path.is_file() {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        return kinds
+            .into_iter()
+            .find(|k| k.file_name() == name)
+            .map(|k| (path.to_path_buf(), k))
+            .ok_or_else(|| anyhow::anyhow!("{} is not a pom.xml, build.gradle or build.gradle.kts", path.display()));
+    }
+
+    kinds
+        .into_iter()
+        .map(|k| (path.join(k.file_name()), k))
+        .find(|(p, _)| p.exists())
+        .ok_or_else(|| anyhow::anyhow!("No pom.xml, build.gradle or build.gradle.kts found in {}", path.display()))
+}
+
+/// Read the build model of the project at `path` without mapping dependencies
+pub fn read_build(path: &Path) -> anyhow::Result<(BuildModel, BuildKind, PathBuf)> {
+    let (build_file, kind) = find_build_file(path)?;
+    let root = build_file.parent().map(Path::to_path_buf).unwrap_or_default();
+    let content = fs::read_to_string(&build_file)?;
+
+    let model = match kind {
+        BuildKind::Maven => maven::parse_pom(&content)?,
+        BuildKind::GradleGroovy | BuildKind::GradleKotlin => {
+            let settings = ["settings.gradle.kts", "settings.gradle"]
+                .iter()
+                .find_map(|f| fs::read_to_string(root.join(f)).ok());
+            gradle::parse_build(&content, settings.as_deref())
+        }
+    };
+
+    Ok((model, kind, root))
+}
+
+/// Import the project at `path`, mapping its artifacts back onto Initializr dependency ids
+pub fn import_project(path: &Path, dependencies: &InitializrDependencies) -> anyhow::Result<ImportedProject> {
+    let (model, kind, root) = read_build(path)?;
+    Ok(to_config(model, kind, &root, dependencies))
+}
+
+pub fn to_config(
+    model: BuildModel,
+    kind: BuildKind,
+    root: &Path,
+    dependencies: &InitializrDependencies,
+) -> ImportedProject {
+    // several ids can share coordinates, pick the first by name so every run agrees
+    let mut sorted: Vec<_> = dependencies.dependencies.iter().collect();
+    sorted.sort_by_key(|(id, _)| id.as_str());
+    let mut by_coordinates: HashMap<(&str, &str), &str> = HashMap::new();
+    for (id, d) in sorted {
+        by_coordinates.entry((d.group_id.as_str(), d.artifact_id.as_str())).or_insert(id.as_str());
+    }
+
+    let mut ids = Vec::new();
+    let mut unmapped = Vec::new();
+    for (group, artifact) in &model.dependencies {
+        match by_coordinates.get(&(group.as_str(), artifact.as_str())) {
+            Some(id) => {
+                if !ids.contains(id) {
+                    ids.push(*id);
+                }
+            }
+            None if IMPLICIT_ARTIFACTS.contains(&artifact.as_str()) => {}
+            None => unmapped.push(format!("{}:{}", group, artifact)),
+        }
+    }
+
+    let group_id = model.group_id.unwrap_or_else(|| "com.example".to_string());
+    let artifact_id = model.artifact_id.unwrap_or_else(|| "demo".to_string());
+    let package_name = find_package_name(root)
+        .unwrap_or_else(|| format!("{}.{}", group_id, artifact_id).replace("-", ""));
+
+    let config = SprintInitConfig {
+        config_version: CONFIG_VERSION,
+        project_type: kind.project_type().to_string(),
+        language: model.language.unwrap_or_else(|| "java".to_string()),
+        packaging: model.packaging.unwrap_or_else(|| "jar".to_string()),
+        configuration_file_format: find_configuration_format(root),
+        java_version: model.java_version.unwrap_or(17),
+        name: model.name.unwrap_or_else(|| artifact_id.clone()),
+        description: model.description.unwrap_or_default(),
+        package_name,
+        dependencies: ids.join(","),
+        boot_version: model.boot_version.unwrap_or_else(|| dependencies.boot_version.clone()),
+        version: model.version.unwrap_or_else(|| "0.0.1-SNAPSHOT".to_string()),
+        group_id,
+        artifact_id,
+        overlays: Vec::new(),
+        application: Default::default(),
+    };
+
+    ImportedProject { config, unmapped }
+}
+
+/// Config of the project at `root` as it is now: the one recorded at generation when there is a
+/// provenance file, with the dependencies and Boot version the build has today
+pub fn current_config(
+    model: BuildModel,
+    kind: BuildKind,
+    root: &Path,
+    dependencies: &InitializrDependencies,
+) -> anyhow::Result<SprintInitConfig> {
+    let imported = to_config(model, kind, root, dependencies).config;
+    Ok(match generator::provenance::read(root)? {
+        Some(recorded) => SprintInitConfig {
+            dependencies: imported.dependencies,
+            boot_version: imported.boot_version,
+            ..recorded
+        },
+        None => imported,
+    })
+}
+
+/// New content for an existing build file, to be reviewed before it is written
+pub struct BuildUpdate {
+    pub path: PathBuf,
+    pub current: String,
+    pub updated: String,
+}
+
+/// Merge the Initializr dependencies `ids` into the build of the project at `path`.
+/// The reference build is generated for the project's own Boot version and coordinates.
+pub async fn plan_dependencies(path: &Path, ids: &[String]) -> anyhow::Result<BuildUpdate> {
+    let dependencies = api::get_dependencies().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let unknown: Vec<&str> = ids
+        .iter()
+        .filter(|id| !dependencies.dependencies.contains_key(id.as_str()))
+        .map(String::as_str)
+        .collect();
+    if !unknown.is_empty() {
+        anyhow::bail!("Unknown dependency ids: {}", unknown.join(", "));
+    }
+
+    let (build_file, kind) = find_build_file(path)?;
+    let (model, _, root) = read_build(&build_file)?;
+    let mut config = current_config(model, kind, &root, &dependencies)?;
+    let mut all: Vec<&str> = config.dependencies.split(',').filter(|d| !d.is_empty()).collect();
+    all.extend(ids.iter().map(String::as_str));
+    config.dependencies = normalize_dependencies(&all.join(","));
+
+    let capabilities = api::get_capabilities().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let reference = generator::fetch_build_file(&capabilities, &config, kind)
+        .await
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+
+    let current = fs::read_to_string(&build_file)?;
+    let updated = match kind {
+        BuildKind::Maven => merge::merge_pom(&current, &reference)?,
+        BuildKind::GradleGroovy | BuildKind::GradleKotlin => merge::merge_gradle(&current, &reference),
+    };
+    Ok(BuildUpdate { path: build_file, current, updated })
+}
+
+fn find_configuration_format(root: &Path) -> String {
+    let resources = root.join("src/main/resources");
+    if resources.join("application.yml").exists() || resources.join("application.yaml").exists() {
+        "yaml".to_string()
+    } else {
+        "properties".to_string()
+    }
+}
+
+/// Package of the class annotated with @SpringBootApplication, if there is one
+fn find_package_name(root: &Path) -> Option<String> {
+    ["java", "kotlin", "groovy"]
+        .iter()
+        .find_map(|lang| find_application_package(&root.join("src/main").join(lang)))
+}
+
+fn find_application_package(dir: &Path) -> Option<String> {
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(pkg) = find_application_package(&path) {
+                return Some(pkg);
+            }
+            continue;
+        }
+        let Ok(source) = fs::read_to_string(&path) else { continue };
+        if !source.contains("@SpringBootApplication") {
+            continue;
+        }
+        return source
+            .lines()
+            .map(str::trim)
+            .find_map(|l| l.strip_prefix("package "))
+            .map(|p| p.trim_end_matches(';').trim().to_string());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::api::Dependency;
+
+    fn dependency(group_id: &str, artifact_id: &str) -> Dependency {
+        Dependency {
+            group_id: group_id.to_string(),
+            artifact_id: artifact_id.to_string(),
+            scope: "compile".to_string(),
+            bom: None,
+            version: None,
+            repository: None,
+        }
+    }
+
+    #[test]
+    fn maps_artifacts_to_initializr_ids_and_reports_the_rest() {
+        let dependencies = InitializrDependencies {
+            boot_version: "3.5.0".to_string(),
+            dependencies: HashMap::from([
+                ("web".to_string(), dependency("org.springframework.boot", "spring-boot-starter-web")),
+                ("lombok".to_string(), dependency("org.projectlombok", "lombok")),
+            ]),
+            boms: HashMap::new(),
+            repositories: HashMap::new(),
+        };
+        let model = BuildModel {
+            group_id: Some("org.turntabl".to_string()),
+            artifact_id: Some("snake".to_string()),
+            dependencies: vec![
+                ("org.springframework.boot".to_string(), "spring-boot-starter-web".to_string()),
+                ("org.springframework.boot".to_string(), "spring-boot-starter-test".to_string()),
+                ("org.projectlombok".to_string(), "lombok".to_string()),
+                ("com.acme".to_string(), "acme-client".to_string()),
+            ],
+            ..Default::default()
+        };
+
+        let imported = to_config(model, BuildKind::GradleKotlin, Path::new("/nonexistent"), &dependencies);
+
+        assert_eq!(imported.config.project_type, "gradle-project-kotlin");
+        assert_eq!(imported.config.dependencies, "web,lombok");
+        assert_eq!(imported.config.boot_version, "3.5.0");
+        assert_eq!(imported.config.package_name, "org.turntabl.snake");
+        assert_eq!(imported.unmapped, vec!["com.acme:acme-client".to_string()]);
+    }
+
+    #[test]
+    fn shared_coordinates_map_to_the_first_id() {
+        let dependencies = InitializrDependencies {
+            boot_version: "3.5.0".to_string(),
+            dependencies: HashMap::from([
+                ("session-redis".to_string(), dependency("org.springframework.session", "spring-session-core")),
+                ("session-jdbc".to_string(), dependency("org.springframework.session", "spring-session-core")),
+            ]),
+            boms: HashMap::new(),
+            repositories: HashMap::new(),
+        };
+        let model = BuildModel {
+            dependencies: vec![("org.springframework.session".to_string(), "spring-session-core".to_string())],
+            ..Default::default()
+        };
+
+        let imported = to_config(model, BuildKind::Maven, Path::new("/nonexistent"), &dependencies);
+
+        assert_eq!(imported.config.dependencies, "session-jdbc");
+    }
+}
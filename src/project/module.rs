@@ -0,0 +1,242 @@
+// adds a freshly generated project to an existing multi-module build: the module lands in a
+// subdirectory and is registered in the parent pom.xml `<modules>` or settings.gradle `include`
+use crate::generator::{self, workspace};
+use crate::project::{self, BuildKind, BuildModel};
+use crate::types::generic::SprintInitConfig;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Build that aggregates the modules of a repository
+pub struct ParentBuild {
+    pub kind: BuildKind,
+    /// pom.xml for maven, settings.gradle(.kts) for gradle
+    pub registry: PathBuf,
+    pub model: BuildModel,
+    /// root build.gradle(.kts), when there is one
+    pub gradle_build: Option<PathBuf>,
+}
+
+const BOOT_PLUGIN: &str = "org.springframework.boot";
+
+pub struct AddedModule {
+    pub dir: PathBuf,
+    pub registry: PathBuf,
+    /// config keys taken from the parent build
+    pub inherited: Vec<&'static str>,
+}
+
+pub fn find_parent(repo: &Path) -> anyhow::Result<ParentBuild> {
+    let pom = repo.join("pom.xml");
+    if pom.exists() {
+        let (model, _, _) = project::read_build(&pom)?;
+        if model.packaging.as_deref() == Some("jar") || model.packaging.as_deref() == Some("war") {
+            anyhow::bail!("{} builds a {}, it cannot aggregate modules", pom.display(), model.packaging.unwrap_or_default());
+        }
+        return Ok(ParentBuild { kind: BuildKind::Maven, registry: pom, model, gradle_build: None });
+    }
+
+    for (settings, kind) in [("settings.gradle.kts", BuildKind::GradleKotlin), ("settings.gradle", BuildKind::GradleGroovy)] {
+        let registry = repo.join(settings);
+        if !registry.exists() {
+            continue;
+        }
+        let gradle_build = Some(repo.join(kind.file_name())).filter(|p| p.exists());
+        let model = match &gradle_build {
+            Some(build) => project::read_build(build)?.0,
+            None => BuildModel::default(),
+        };
+        return Ok(ParentBuild { kind, registry, model, gradle_build });
+    }
+
+    anyhow::bail!("No pom.xml or settings.gradle(.kts) found in {}", repo.display())
+}
+
+/// Copy group id, version and Java version of the parent build into `config`
+pub fn inherit(config: &mut SprintInitConfig, parent: &BuildModel) -> Vec<&'static str> {
+    let mut inherited = Vec::new();
+    if let Some(group_id) = &parent.group_id {
+        config.package_name = format!("{}.{}", group_id, config.artifact_id).replace("-", "");
+        config.group_id = group_id.clone();
+        inherited.push("groupId");
+    }
+    if let Some(version) = &parent.version {
+        config.version = version.clone();
+        inherited.push("version");
+    }
+    if let Some(java_version) = parent.java_version {
+        config.java_version = java_version;
+        inherited.push("javaVersion");
+    }
+    inherited
+}
+
+/// Add `<module>name</module>` to a parent pom, creating `<modules>` when needed
+pub fn register_maven_module(pom: &str, name: &str) -> anyhow::Result<String> {
+    let entry = format!("<module>{}</module>", name);
+    if pom.contains(&entry) {
+        return Ok(pom.to_string());
+    }
+    if let Some(end) = pom.find("</modules>") {
+        let indent: String = pom[..end].rsplit('\n').next().unwrap_or_default().to_string();
+        return Ok(format!("{}\t{}\n{}{}", &pom[..end], entry, indent, &pom[end..]));
+    }
+    let end = pom.rfind("</project>").ok_or_else(|| anyhow::anyhow!("pom.xml has no </project>"))?;
+    Ok(format!("{}\t<modules>\n\t\t{}\n\t</modules>\n{}", &pom[..end], entry, &pom[end..]))
+}
+
+/// Append an `include` for `name` to a settings.gradle(.kts)
+pub fn register_gradle_module(settings: &str, kind: BuildKind, name: &str) -> String {
+    let included = settings
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.starts_with("include"))
+        .any(|l| l.contains(&format!("'{}'", name)) || l.contains(&format!("\"{}\"", name)));
+    if included {
+        return settings.to_string();
+    }
+
+    let mut settings = settings.to_string();
+    if !settings.is_empty() && !settings.ends_with('\n') {
+        settings.push('\n');
+    }
+    match kind {
+        BuildKind::GradleKotlin => settings.push_str(&format!("include(\"{}\")\n", name)),
+        _ => settings.push_str(&format!("include '{}'\n", name)),
+    }
+    settings
+}
+
+/// Module build without the plugin versions the root build already declares. Plugins the root
+/// declares with `apply false` must not repeat their version, and the Spring Boot plugin takes the
+/// root's version whatever version the module was generated with
+pub fn fit_gradle_build(root: &str, build: &str) -> String {
+    let root = root.replace(" apply false", "");
+    let mut plugins = workspace::shared_plugins(&[root.clone(), build.to_string()]);
+    if !workspace::plugin_declarations(&root, BOOT_PLUGIN).is_empty() {
+        plugins.extend(workspace::plugin_declarations(build, BOOT_PLUGIN));
+    }
+    workspace::strip_plugin_versions(build, &plugins)
+}
+
+/// Generate `config` into `repo/<module>` and register it with the parent build
+pub async fn add_module(
+    repo: &Path,
+    mut config: SprintInitConfig,
+    module: Option<String>,
+    inherit_parent: bool,
+    hooks: Option<&generator::hooks::HookCommands>,
+) -> anyhow::Result<AddedModule> {
+    let parent = find_parent(repo)?;
+    config.project_type = parent.kind.project_type().to_string();
+    let inherited = if inherit_parent { inherit(&mut config, &parent.model) } else { Vec::new() };
+
+    let name = module.unwrap_or_else(|| config.artifact_id.clone());
+    let dir = repo.join(&name);
+    if dir.exists() {
+        anyhow::bail!("{} already exists", dir.display());
+    }
+
+    generator::generate_project_into(&config, true, &dir, None, hooks)
+        .await
+        .map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    // the repository already has a wrapper and, for gradle, the settings file
+    let leftovers = [
+        format!("{}.zip", config.artifact_id),
+        "mvnw".into(),
+        "mvnw.cmd".into(),
+        ".mvn".into(),
+        "gradlew".into(),
+        "gradlew.bat".into(),
+        "gradle".into(),
+        "settings.gradle".into(),
+        "settings.gradle.kts".into(),
+    ];
+    for leftover in leftovers {
+        let path = dir.join(leftover);
+        if path.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else if path.exists() {
+            fs::remove_file(&path)?;
+        }
+    }
+
+    let registry = fs::read_to_string(&parent.registry)?;
+    match parent.kind {
+        BuildKind::Maven => {
+            // modules of a boot-parented aggregator inherit the boot parent through it, an
+            // aggregator importing the boot bom leaves the module its own starter parent
+            if parent.model.boot_parent {
+                let settings = workspace::WorkspaceSettings {
+                    group_id: parent.model.group_id.clone(),
+                    artifact_id: parent.model.artifact_id.clone(),
+                    version: parent.model.version.clone(),
+                    name: None,
+                };
+                let pom_path = dir.join("pom.xml");
+                if let Some(pom) = workspace::reparent_pom(&fs::read_to_string(&pom_path)?, &settings) {
+                    fs::write(&pom_path, pom)?;
+                }
+            }
+            fs::write(&parent.registry, register_maven_module(&registry, &name)?)?;
+        }
+        BuildKind::GradleGroovy | BuildKind::GradleKotlin => {
+            if let Some(root_build) = &parent.gradle_build {
+                let module_build = dir.join(parent.kind.file_name());
+                let build = fs::read_to_string(&module_build)?;
+                fs::write(&module_build, fit_gradle_build(&fs::read_to_string(root_build)?, &build))?;
+            }
+            fs::write(&parent.registry, register_gradle_module(&registry, parent.kind, &name))?;
+        }
+    }
+
+    Ok(AddedModule { dir, registry: parent.registry, inherited })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_modules_once() {
+        let pom = "<project>\n\t<modules>\n\t\t<module>orders</module>\n\t</modules>\n</project>\n";
+
+        let registered = register_maven_module(pom, "billing").unwrap();
+
+        assert!(registered.contains("\t\t<module>orders</module>\n\t\t<module>billing</module>\n\t</modules>"));
+        assert_eq!(register_maven_module(&registered, "billing").unwrap(), registered);
+
+        let settings = "rootProject.name = 'platform'\ninclude 'orders'\n";
+        let registered = register_gradle_module(settings, BuildKind::GradleGroovy, "billing");
+        assert!(registered.ends_with("include 'orders'\ninclude 'billing'\n"));
+        assert_eq!(register_gradle_module(&registered, BuildKind::GradleGroovy, "orders"), registered);
+    }
+
+    #[test]
+    fn inherits_parent_coordinates() {
+        let parent = BuildModel {
+            group_id: Some("com.acme".to_string()),
+            version: Some("2.1.0".to_string()),
+            java_version: Some(21),
+            ..Default::default()
+        };
+        let mut config = SprintInitConfig { artifact_id: "billing-api".to_string(), ..Default::default() };
+
+        let inherited = inherit(&mut config, &parent);
+
+        assert_eq!(inherited, vec!["groupId", "version", "javaVersion"]);
+        assert_eq!(config.package_name, "com.acme.billingapi");
+        assert_eq!(config.java_version, 21);
+    }
+
+    #[test]
+    fn module_builds_take_the_boot_plugin_version_of_the_root() {
+        let root = "plugins {\n\tid 'java'\n\tid 'org.springframework.boot' version '3.4.2' apply false\n\tid 'io.spring.dependency-management' version '1.1.7' apply false\n}\n";
+        let build = "plugins {\n\tid 'java'\n\tid 'org.springframework.boot' version '3.5.10'\n\tid 'io.spring.dependency-management' version '1.1.7'\n}\n";
+
+        let fitted = fit_gradle_build(root, build);
+
+        assert_eq!(fitted, "plugins {\n\tid 'java'\n\tid 'org.springframework.boot'\n\tid 'io.spring.dependency-management'\n}\n");
+        let unrelated = "plugins {\n\tid 'base'\n}\n";
+        assert_eq!(fit_gradle_build(unrelated, build), build);
+    }
+}
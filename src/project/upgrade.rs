@@ -0,0 +1,305 @@
+// upgrade assistant: the project skeleton is generated twice, for the current and the target
+// boot version, and the differences in build, wrapper and config files become hunks that can
+// be applied to the real project one by one
+use crate::api;
+use crate::generator;
+use crate::project::diff::{self, Line};
+use crate::types::generic::SprintInitConfig;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files compared between the two generations, relative to the project root
+const UPGRADE_FILES: &[&str] = &[
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "settings.gradle",
+    "settings.gradle.kts",
+    "mvnw",
+    "mvnw.cmd",
+    ".mvn/wrapper/maven-wrapper.properties",
+    "gradlew",
+    "gradlew.bat",
+    "gradle/wrapper/gradle-wrapper.properties",
+    "src/main/resources/application.properties",
+    "src/main/resources/application.yaml",
+    "src/main/resources/application.yml",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpgradeHunk {
+    /// file the hunk belongs to, relative to the project root
+    pub file: String,
+    pub header: String,
+    /// diff lines prefixed with ' ', '-' or '+'
+    pub lines: Vec<String>,
+    /// position in the old generation, used when the block is empty
+    pub old_start: usize,
+}
+
+impl UpgradeHunk {
+    /// Lines the hunk expects to find in the project
+    pub fn before(&self) -> Vec<&str> {
+        self.lines.iter().filter(|l| !l.starts_with('+')).map(|l| &l[1..]).collect()
+    }
+
+    /// Lines the hunk leaves behind
+    pub fn after(&self) -> Vec<&str> {
+        self.lines.iter().filter(|l| !l.starts_with('-')).map(|l| &l[1..]).collect()
+    }
+}
+
+pub struct UpgradePlan {
+    pub root: PathBuf,
+    pub from: String,
+    pub to: String,
+    pub hunks: Vec<UpgradeHunk>,
+    /// freshly generated target versions of the compared files, for permissions of new files
+    generated: PathBuf,
+    /// holds both skeletons until the plan is dropped
+    _scratch: ScratchDir,
+}
+
+/// Directory removed with everything in it when dropped
+struct ScratchDir(PathBuf);
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Hunks turning `old` into `new`, for `file`
+pub fn file_hunks(file: &str, old: &str, new: &str) -> Vec<UpgradeHunk> {
+    let lines = diff::diff_lines(old, new);
+    diff::hunks(&lines, 3)
+        .into_iter()
+        .map(|hunk| UpgradeHunk {
+            file: file.to_string(),
+            header: hunk.header(),
+            old_start: hunk.old_start,
+            lines: hunk
+                .lines
+                .iter()
+                .map(|l| match l {
+                    Line::Same(l) => format!(" {}", l),
+                    Line::Removed(l) => format!("-{}", l),
+                    Line::Added(l) => format!("+{}", l),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// "\r\n" when `text` uses Windows line endings, as mvnw.cmd and gradlew.bat do
+fn line_ending(text: &str) -> &'static str {
+    if text.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+/// Apply `hunks` (all of the same file, in file order) to `content`, keeping its line endings.
+/// Returns the hunks whose expected lines were not found.
+pub fn apply_hunks<'a>(content: &str, hunks: &[&'a UpgradeHunk]) -> (String, Vec<&'a UpgradeHunk>) {
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+    let mut rejected = Vec::new();
+    let mut cursor = 0;
+    // lines added or removed by earlier hunks move the recorded positions
+    let mut shift: isize = 0;
+
+    for hunk in hunks {
+        let before = hunk.before();
+        let after: Vec<String> = hunk.after().into_iter().map(String::from).collect();
+        let at = if before.is_empty() {
+            Some(((hunk.old_start as isize + shift).max(0) as usize).clamp(cursor, lines.len()))
+        } else {
+            lines
+                .get(cursor..)
+                .and_then(|rest| rest.windows(before.len()).position(|w| w.iter().zip(&before).all(|(a, b)| a == b)))
+                .map(|i| i + cursor)
+        };
+        match at {
+            Some(at) => {
+                let added = after.len();
+                lines.splice(at..at + before.len(), after);
+                cursor = at + added;
+                shift += added as isize - before.len() as isize;
+            }
+            None => rejected.push(*hunk),
+        }
+    }
+
+    let eol = line_ending(content);
+    let mut patched = lines.join(eol);
+    if !patched.is_empty() {
+        patched.push_str(eol);
+    }
+    (patched, rejected)
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+/// Generate the project at `root` for its current boot version (or `from`) and for `to`,
+/// and collect the hunks between the two
+pub async fn plan(root: &Path, from: Option<String>, to: &str) -> anyhow::Result<UpgradePlan> {
+    let dependencies = api::get_dependencies().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    let (model, kind, build_root) = super::read_build(root)?;
+    let current_boot = model.boot_version.clone();
+    let config = super::current_config(model, kind, &build_root, &dependencies)?;
+    let from = from
+        .or(current_boot)
+        .ok_or_else(|| anyhow::anyhow!("Could not find the current Spring Boot version, pass --from"))?;
+
+    let scratch = ScratchDir(std::env::temp_dir().join(format!("spring-tui-upgrade-{}", std::process::id())));
+    let old_dir = scratch.0.join("old");
+    let new_dir = scratch.0.join("new");
+    let capabilities = api::get_capabilities().await.map_err(|e| anyhow::anyhow!(e.to_string()))?;
+    for (dir, boot_version) in [(&old_dir, &from), (&new_dir, &to.to_string())] {
+        let generation = SprintInitConfig { boot_version: boot_version.clone(), ..config.clone() };
+        generator::generate_project_with(&capabilities, &generation, true, dir, None, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("Generating Spring Boot {} failed: {}", boot_version, e))?;
+    }
+
+    let hunks = UPGRADE_FILES
+        .iter()
+        .flat_map(|file| file_hunks(file, &read(&old_dir.join(file)), &read(&new_dir.join(file))))
+        .collect();
+
+    Ok(UpgradePlan { root: build_root, from, to: to.to_string(), hunks, generated: new_dir, _scratch: scratch })
+}
+
+impl UpgradePlan {
+    /// Unified diff of every hunk, numbered so they can be picked with `--apply`
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut file = "";
+        for (i, hunk) in self.hunks.iter().enumerate() {
+            if hunk.file != file {
+                file = &hunk.file;
+                out.push_str(&format!("--- a/{}\n+++ b/{}\n", file, file));
+            }
+            out.push_str(&format!("{} [{}]\n", hunk.header, i + 1));
+            for line in &hunk.lines {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Apply the hunks at `selected` (0 based) to the project.
+    /// Returns the files written and the hunks that no longer match the project.
+    pub fn apply(&self, selected: &[usize]) -> anyhow::Result<(Vec<String>, Vec<&UpgradeHunk>)> {
+        let mut written = Vec::new();
+        let mut rejected = Vec::new();
+        // hunks are grouped by file, so consecutive duplicates are all there is to drop
+        let mut files: Vec<&str> = self
+            .hunks
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| selected.contains(i))
+            .map(|(_, h)| h.file.as_str())
+            .collect();
+        files.dedup();
+
+        for file in files {
+            let hunks: Vec<&UpgradeHunk> = self
+                .hunks
+                .iter()
+                .enumerate()
+                .filter(|(i, h)| h.file == file && selected.contains(i))
+                .map(|(_, h)| h)
+                .collect();
+            let path = self.root.join(file);
+            let existed = path.exists();
+            let (mut patched, failed) = apply_hunks(&read(&path), &hunks);
+            let all_failed = failed.len() == hunks.len();
+            rejected.extend(failed);
+            if all_failed {
+                continue;
+            }
+
+            if !existed && line_ending(&read(&self.generated.join(file))) == "\r\n" {
+                patched = patched.replace('\n', "\r\n");
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, patched)?;
+            // keep new wrapper scripts executable
+            if !existed && let Ok(metadata) = fs::metadata(self.generated.join(file)) {
+                fs::set_permissions(&path, metadata.permissions())?;
+            }
+            written.push(file.to_string());
+        }
+        Ok((written, rejected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "<parent>\n<version>3.4.1</version>\n</parent>\n<java.version>17</java.version>\n";
+    const NEW: &str = "<parent>\n<version>3.5.0</version>\n</parent>\n<java.version>17</java.version>\n";
+
+    #[test]
+    fn applies_hunks_to_a_customised_file() {
+        let hunks = file_hunks("pom.xml", OLD, NEW);
+        let project = "<!-- ours -->\n<parent>\n<version>3.4.1</version>\n</parent>\n<java.version>17</java.version>\n<extra/>\n";
+
+        let (patched, rejected) = apply_hunks(project, &hunks.iter().collect::<Vec<_>>());
+
+        assert_eq!(hunks.len(), 1);
+        assert!(rejected.is_empty());
+        assert!(patched.contains("<version>3.5.0</version>"));
+        assert!(patched.starts_with("<!-- ours -->\n") && patched.ends_with("<extra/>\n"));
+    }
+
+    #[test]
+    fn reports_hunks_that_no_longer_match() {
+        let hunks = file_hunks("pom.xml", OLD, NEW);
+        let project = "<parent>\n<version>3.3.0</version>\n</parent>\n";
+
+        let (patched, rejected) = apply_hunks(project, &hunks.iter().collect::<Vec<_>>());
+
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(patched, project);
+    }
+
+    #[test]
+    fn new_files_are_created_from_scratch() {
+        let hunks = file_hunks("gradle/wrapper/gradle-wrapper.properties", "", "distributionUrl=x\n");
+
+        let (patched, rejected) = apply_hunks("", &hunks.iter().collect::<Vec<_>>());
+
+        assert!(rejected.is_empty());
+        assert_eq!(patched, "distributionUrl=x\n");
+    }
+
+    #[test]
+    fn crlf_files_keep_their_line_endings() {
+        let old = "@REM wrapper\r\nset MAVEN_VERSION=3.9.9\r\n@REM end\r\n";
+        let new = "@REM wrapper\r\nset MAVEN_VERSION=3.9.11\r\n@REM end\r\n";
+        let hunks = file_hunks("mvnw.cmd", old, new);
+
+        let (patched, rejected) = apply_hunks(old, &hunks.iter().collect::<Vec<_>>());
+
+        assert!(rejected.is_empty());
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn scratch_dir_goes_away_on_every_exit() {
+        let path = std::env::temp_dir().join(format!("spring-tui-scratch-{}", std::process::id()));
+        let failed: anyhow::Result<()> = (|| {
+            let scratch = ScratchDir(path.clone());
+            fs::create_dir_all(scratch.0.join("old"))?;
+            anyhow::bail!("generation failed")
+        })();
+
+        assert!(failed.is_err());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,266 @@
+// application settings pane: server port, application name, datasource url, active profiles and
+// free form properties, written to the project's application config after extraction
+use super::{centered_rect, ACCENT_COLOR, BG_COLOR, INPUT_BG, MUTED_COLOR, TEXT_COLOR};
+use crate::types::generic::ApplicationSettings;
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Row {
+    ServerPort,
+    ApplicationName,
+    DatasourceUrl,
+    ActiveProfiles,
+    /// property of the main file (profile None) or of a profile file
+    Property(Option<String>, String),
+    Add,
+}
+
+fn rows(settings: &ApplicationSettings) -> Vec<Row> {
+    let mut rows = vec![Row::ServerPort, Row::ApplicationName, Row::DatasourceUrl, Row::ActiveProfiles];
+    rows.extend(settings.properties.keys().map(|k| Row::Property(None, k.clone())));
+    for (profile, properties) in &settings.profiles {
+        rows.extend(properties.keys().map(|k| Row::Property(Some(profile.clone()), k.clone())));
+    }
+    rows.push(Row::Add);
+    rows
+}
+
+/// `key=value` or `profile:key=value`
+fn parse_property(input: &str) -> Result<(Option<String>, String, String), String> {
+    let (target, value) = input.split_once('=').ok_or("expected key=value or profile:key=value")?;
+    let (profile, key) = match target.split_once(':') {
+        Some((profile, key)) => (Some(profile.trim().to_string()), key.trim()),
+        None => (None, target.trim()),
+    };
+    if key.is_empty() || profile.as_deref() == Some("") {
+        return Err("expected key=value or profile:key=value".to_string());
+    }
+    Ok((profile, key.to_string(), value.trim().to_string()))
+}
+
+fn property_text(profile: &Option<String>, key: &str, value: &str) -> String {
+    match profile {
+        Some(profile) => format!("{}:{}={}", profile, key, value),
+        None => format!("{}={}", key, value),
+    }
+}
+
+#[derive(Default)]
+pub struct Editor {
+    list_state: ListState,
+    /// input buffer while a row is edited
+    editing: Option<String>,
+    error: Option<String>,
+}
+
+impl Editor {
+    fn current(&self, settings: &ApplicationSettings) -> Row {
+        let rows = rows(settings);
+        let i = self.list_state.selected().unwrap_or(0).min(rows.len() - 1);
+        rows[i].clone()
+    }
+
+    fn value(settings: &ApplicationSettings, row: &Row) -> String {
+        match row {
+            Row::ServerPort => settings.server_port.map(|p| p.to_string()).unwrap_or_default(),
+            Row::ApplicationName => settings.application_name.clone().unwrap_or_default(),
+            Row::DatasourceUrl => settings.datasource_url.clone().unwrap_or_default(),
+            Row::ActiveProfiles => settings.active_profiles.join(","),
+            Row::Property(None, key) => property_text(&None, key, &settings.properties[key]),
+            Row::Property(Some(profile), key) => property_text(&Some(profile.clone()), key, &settings.profiles[profile][key]),
+            Row::Add => String::new(),
+        }
+    }
+
+    fn remove(settings: &mut ApplicationSettings, row: &Row) {
+        match row {
+            Row::ServerPort => settings.server_port = None,
+            Row::ApplicationName => settings.application_name = None,
+            Row::DatasourceUrl => settings.datasource_url = None,
+            Row::ActiveProfiles => settings.active_profiles.clear(),
+            Row::Property(None, key) => {
+                settings.properties.remove(key);
+            }
+            Row::Property(Some(profile), key) => {
+                if let Some(properties) = settings.profiles.get_mut(profile) {
+                    properties.remove(key);
+                    if properties.is_empty() {
+                        settings.profiles.remove(profile);
+                    }
+                }
+            }
+            Row::Add => {}
+        }
+    }
+
+    fn commit(settings: &mut ApplicationSettings, row: &Row, input: &str) -> Result<(), String> {
+        let input = input.trim();
+        let optional = |s: &str| (!s.is_empty()).then(|| s.to_string());
+        match row {
+            Row::ServerPort if input.is_empty() => settings.server_port = None,
+            Row::ServerPort => {
+                settings.server_port = Some(input.parse().map_err(|_| format!("'{}' is not a port number", input))?)
+            }
+            Row::ApplicationName => settings.application_name = optional(input),
+            Row::DatasourceUrl => settings.datasource_url = optional(input),
+            Row::ActiveProfiles => {
+                settings.active_profiles =
+                    input.split(',').map(str::trim).filter(|p| !p.is_empty()).map(String::from).collect()
+            }
+            Row::Property(..) | Row::Add => {
+                if input.is_empty() {
+                    Self::remove(settings, row);
+                    return Ok(());
+                }
+                let (profile, key, value) = parse_property(input)?;
+                Self::remove(settings, row);
+                match profile {
+                    Some(profile) => {
+                        settings.profiles.entry(profile).or_default().insert(key, value);
+                    }
+                    None => {
+                        settings.properties.insert(key, value);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a key; returns false once the pane is closed
+    pub fn handle_key(&mut self, settings: &mut ApplicationSettings, key: KeyCode) -> bool {
+        let len = rows(settings).len();
+        if let Some(buffer) = &mut self.editing {
+            match key {
+                KeyCode::Enter => {
+                    let input = buffer.clone();
+                    match Self::commit(settings, &self.current(settings), &input) {
+                        Ok(()) => {
+                            self.editing = None;
+                            self.error = None;
+                        }
+                        Err(e) => self.error = Some(e),
+                    }
+                }
+                KeyCode::Esc => {
+                    self.editing = None;
+                    self.error = None;
+                }
+                KeyCode::Char(c) => buffer.push(c),
+                KeyCode::Backspace => {
+                    buffer.pop();
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        let i = self.list_state.selected().unwrap_or(0);
+        match key {
+            KeyCode::Esc | KeyCode::Char('A') => return false,
+            KeyCode::Down | KeyCode::Char('j') => self.list_state.select(Some((i + 1) % len)),
+            KeyCode::Up | KeyCode::Char('k') => self.list_state.select(Some((i + len - 1) % len)),
+            KeyCode::Enter => self.editing = Some(Self::value(settings, &self.current(settings))),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                Self::remove(settings, &self.current(settings));
+                self.list_state.select(Some(i.min(rows(settings).len() - 1)));
+            }
+            _ => {}
+        }
+        true
+    }
+
+    pub fn render(&mut self, f: &mut Frame<'_>, settings: &ApplicationSettings, format: &str) {
+        if self.list_state.selected().is_none() {
+            self.list_state.select(Some(0));
+        }
+        let area = centered_rect(70, 60, f.area());
+        f.render_widget(Clear, area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .style(Style::default().bg(BG_COLOR).fg(TEXT_COLOR))
+            .title(format!(" Application Settings ({}) ", format));
+        f.render_widget(block.clone(), area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(3), Constraint::Length(1), Constraint::Length(1)])
+            .split(block.inner(area));
+
+        let current = self.list_state.selected().unwrap_or(0);
+        let items: Vec<ListItem> = rows(settings)
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let label = match row {
+                    Row::ServerPort => "Server port".to_string(),
+                    Row::ApplicationName => "Application name".to_string(),
+                    Row::DatasourceUrl => "Datasource URL".to_string(),
+                    Row::ActiveProfiles => "Active profiles".to_string(),
+                    Row::Property(None, _) => "Property".to_string(),
+                    Row::Property(Some(profile), _) => format!("application-{}", profile),
+                    Row::Add => "+ Add property".to_string(),
+                };
+                let value = match (&self.editing, i == current) {
+                    (Some(buffer), true) => format!("{}█", buffer),
+                    // the profile is in the label already
+                    _ => match row {
+                        Row::Property(None, key) => format!("{}={}", key, settings.properties[key]),
+                        Row::Property(Some(profile), key) => format!("{}={}", key, settings.profiles[profile][key]),
+                        _ => Self::value(settings, row),
+                    },
+                };
+                let value_style = if value.is_empty() { Style::default().fg(MUTED_COLOR) } else { Style::default().fg(TEXT_COLOR) };
+                let value = if value.is_empty() && !matches!(row, Row::Add) { "(not set)".to_string() } else { value };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{:<24}", label), Style::default().fg(ACCENT_COLOR)),
+                    Span::styled(value, value_style),
+                ]))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(Style::default().bg(INPUT_BG).add_modifier(Modifier::BOLD));
+        f.render_stateful_widget(list, chunks[0], &mut self.list_state);
+
+        if let Some(error) = &self.error {
+            f.render_widget(Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)), chunks[1]);
+        }
+        let help = if self.editing.is_some() {
+            "Enter: Save | Esc: Cancel | properties as key=value or profile:key=value"
+        } else {
+            "↑↓: Move | Enter: Edit | d: Clear | Esc: Close"
+        };
+        f.render_widget(Paragraph::new(help).style(Style::default().fg(MUTED_COLOR)), chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits_settings_and_profile_properties() {
+        let mut settings = ApplicationSettings::default();
+
+        Editor::commit(&mut settings, &Row::ServerPort, "8081").unwrap();
+        Editor::commit(&mut settings, &Row::ActiveProfiles, "dev, local").unwrap();
+        Editor::commit(&mut settings, &Row::Add, "dev:logging.level.root = debug").unwrap();
+        let row = Row::Property(Some("dev".to_string()), "logging.level.root".to_string());
+        Editor::commit(&mut settings, &row, "management.port=9000").unwrap();
+
+        assert_eq!(settings.server_port, Some(8081));
+        assert_eq!(settings.active_profiles, vec!["dev", "local"]);
+        assert!(settings.profiles.is_empty());
+        assert_eq!(settings.properties["management.port"], "9000");
+        assert!(Editor::commit(&mut settings, &Row::ServerPort, "http").is_err());
+        assert!(Editor::commit(&mut settings, &Row::Add, "no value").is_err());
+    }
+}
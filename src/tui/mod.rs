@@ -1,10 +1,18 @@
+pub mod application;
+pub mod upgrade;
+
 use crate::api;
 use crate::generator;
+use crate::generator::command::CommandStyle;
+use crate::generator::git::GitOptions;
+use crate::generator::hooks::HookCommands;
+use crate::generator::overlay;
+use crate::generator::share;
 use crate::types::api::{InitializrCapabilities, InitializrDependencies};
 use crate::types::generic::SprintInitConfig;
-use crate::types::config::FileType;
+use crate::types::config::{FileType, GlobalConfig};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -27,6 +35,8 @@ const TEXT_COLOR: Color = Color::White;
 const MUTED_COLOR: Color = Color::DarkGray;
 const INPUT_BG: Color = Color::Rgb(40, 44, 52);
 
+const EXPORT_FORMATS: [&str; 6] = ["YAML", "JSON", "TOML", "curl", "HTTPie", "spring init"];
+
 #[derive(Clone, Copy, PartialEq)]
 enum Field {
     ProjectType,
@@ -66,79 +76,31 @@ struct App {
     show_popup: bool,
     show_export_popup: bool,
     export_filename: String,
-    export_format_idx: usize, // 0=Yaml, 1=Json, 2=Toml
+    export_format_idx: usize, // 0=Yaml, 1=Json, 2=Toml, 3=curl, 4=HTTPie, 5=spring init
     export_focus_filename: bool,
     extract_project: bool,
+    /// run the git step after extraction
+    git_init: bool,
+    git_options: GitOptions,
+    /// hooks run by the generations started here
+    hooks: Option<HookCommands>,
+    output_dir: String,
     show_config_popup: bool,
+    /// application settings pane, open while Some
+    application_editor: Option<application::Editor>,
+    /// 0 = extract toggle, 1 = git toggle, then one row per overlay_choices entry
+    config_popup_idx: usize,
     show_message_popup: bool,
     message_popup_title: String,
     message_popup_text: String,
     message_popup_is_error: bool,
+    show_paste_popup: bool,
+    paste_buffer: String,
 }
 
 impl App {
     fn new(capabilities: InitializrCapabilities, dependencies: InitializrDependencies) -> Self {
-        let config = SprintInitConfig {
-            project_type: capabilities
-                .project_type
-                .as_ref()
-                .and_then(|pt| pt.default.clone())
-                .unwrap_or_else(|| "maven-project".to_string()),
-            language: capabilities
-                .language
-                .as_ref()
-                .and_then(|l| l.default.clone())
-                .unwrap_or_else(|| "java".to_string()),
-            platform_version: "".to_string(),
-            packaging: capabilities
-                .packaging
-                .as_ref()
-                .and_then(|p| p.default.clone())
-                .unwrap_or_else(|| "jar".to_string()),
-            configuration_file_format: "properties".to_string(),
-            java_version: capabilities
-                .java_version
-                .as_ref()
-                .and_then(|jv| jv.default.as_ref())
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(17),
-            group_id: capabilities
-                .group_id
-                .as_ref()
-                .and_then(|g| g.default.clone())
-                .unwrap_or_else(|| "com.example".to_string()),
-            artifact_id: capabilities
-                .artifact_id
-                .as_ref()
-                .and_then(|a| a.default.clone())
-                .unwrap_or_else(|| "demo".to_string()),
-            name: capabilities
-                .name
-                .as_ref()
-                .and_then(|n| n.default.clone())
-                .unwrap_or_else(|| "demo".to_string()),
-            description: capabilities
-                .description
-                .as_ref()
-                .and_then(|d| d.default.clone())
-                .unwrap_or_else(|| "Demo project for Spring Boot".to_string()),
-            package_name: capabilities
-                .package_name
-                .as_ref()
-                .and_then(|p| p.default.clone())
-                .unwrap_or_else(|| "com.example.demo".to_string()),
-            dependencies: "".to_string(),
-            boot_version: capabilities
-                .boot_version
-                .as_ref()
-                .and_then(|bv| bv.default.clone())
-                .unwrap_or_else(|| "3.2.0".to_string()),
-            version: capabilities
-                .version
-                .as_ref()
-                .and_then(|v| v.default.clone())
-                .unwrap_or_else(|| "0.0.1-SNAPSHOT".to_string()),
-        };
+        let config = SprintInitConfig::from_capabilities(&capabilities);
 
         let mut list_state = ListState::default();
         list_state.select(Some(0));
@@ -157,21 +119,88 @@ impl App {
             input_buffer: String::new(),
             deps_search: String::new(),
             selected_deps: BTreeSet::new(),
-            status_message: "<Tab> Switch Pane  <> Navigate  <Enter> Select/Edit  <Shift+c> Config Menu  <Ctrl+q> Exit".to_string(),
+            status_message: "<Tab> Switch Pane  <> Navigate  <Enter> Select/Edit  <Shift+c> Config Menu  <Shift+a> App Settings  <Ctrl+q> Exit".to_string(),
             show_popup: false,
             show_export_popup: false,
             export_filename: "config".to_string(),
             export_format_idx: 0,
             export_focus_filename: true,
             extract_project: false,
+            git_init: false,
+            git_options: GitOptions::default(),
+            hooks: None,
+            output_dir: ".".to_string(),
             show_config_popup: false,
+            application_editor: None,
+            config_popup_idx: 0,
             show_message_popup: false,
             message_popup_title: String::new(),
             message_popup_text: String::new(),
             message_popup_is_error: false,
+            show_paste_popup: false,
+            paste_buffer: String::new(),
         }
     }
 
+    /// Start from an existing config instead of the capability defaults
+    fn with_config(mut self, config: SprintInitConfig) -> Self {
+        self.load_config(config);
+        self
+    }
+
+    fn load_config(&mut self, config: SprintInitConfig) {
+        self.selected_deps = config
+            .dependencies
+            .split(',')
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .map(String::from)
+            .collect();
+        self.config = config;
+    }
+
+    /// Overlays that can be toggled: the installed ones plus any path picked in the config
+    fn overlay_choices(&self) -> Vec<String> {
+        let mut choices = overlay::available();
+        for selected in &self.config.overlays {
+            if !choices.contains(selected) {
+                choices.push(selected.clone());
+            }
+        }
+        choices
+    }
+
+    fn toggle_overlay(&mut self, overlay: String) {
+        if let Some(i) = self.config.overlays.iter().position(|o| *o == overlay) {
+            self.config.overlays.remove(i);
+        } else {
+            self.config.overlays.push(overlay);
+        }
+    }
+
+    /// Replace the current setup with the one encoded in the pasted share link
+    fn apply_share_link(&mut self) {
+        match share::parse_share_link(self.paste_buffer.trim()) {
+            Ok(mut config) => {
+                // share links only carry Initializr parameters
+                config.overlays = std::mem::take(&mut self.config.overlays);
+                config.application = std::mem::take(&mut self.config.application);
+                self.load_config(config);
+                self.status_message = "Share link loaded!".to_string();
+            }
+            Err(e) => self.show_message("Invalid Share Link", e.to_string(), true),
+        }
+        self.show_paste_popup = false;
+        self.paste_buffer.clear();
+    }
+
+    fn show_message(&mut self, title: &str, text: String, is_error: bool) {
+        self.show_message_popup = true;
+        self.message_popup_title = title.to_string();
+        self.message_popup_text = text;
+        self.message_popup_is_error = is_error;
+    }
+
     fn toggle_pane(&mut self) {
         self.active_pane = match self.active_pane {
             ActivePane::Config => ActivePane::Dependencies,
@@ -316,6 +345,7 @@ impl App {
         list.into_iter().filter(|name| name.to_lowercase().contains(&needle)).collect()
     }
 
+    #[allow(clippy::collapsible_if)]
     fn toggle_dependency(&mut self) {
         let options = self.dependency_options();
         if let Some(selected) = self.deps_list_state.selected() {
@@ -361,6 +391,12 @@ fn ui(f: &mut Frame<'_>, app: &mut App) {
     if app.show_config_popup {
         render_config_popup(f, app);
     }
+    if let Some(editor) = &mut app.application_editor {
+        editor.render(f, &app.config.application, &app.config.configuration_file_format);
+    }
+    if app.show_paste_popup {
+        render_paste_popup(f, app);
+    }
     if app.show_message_popup {
         render_message_popup(f, app);
     }
@@ -716,7 +752,7 @@ fn render_export_popup(f: &mut Frame<'_>, app: &mut App) {
     let fmt_label_style = if !app.export_focus_filename { Style::default().fg(ACCENT_COLOR) } else { Style::default().fg(TEXT_COLOR) };
     f.render_widget(Paragraph::new("Format:").style(fmt_label_style), chunks[2]);
     
-    let fmts = ["YAML", "JSON", "TOML"];
+    let fmts = EXPORT_FORMATS;
     let mut spans = Vec::new();
     for (i, fmt) in fmts.iter().enumerate() {
         let is_focused = !app.export_focus_filename;
@@ -733,14 +769,15 @@ fn render_export_popup(f: &mut Frame<'_>, app: &mut App) {
         spans.push(Span::raw("   "));
     }
     
-    f.render_widget(Paragraph::new(Line::from(spans)), chunks[3]);
+    f.render_widget(Paragraph::new(Line::from(spans)).wrap(Wrap { trim: true }), chunks[3]);
 
     // Help
     f.render_widget(Paragraph::new("Tab: Focus | ← →: Format | Enter: Save | Esc: Cancel").alignment(Alignment::Center).style(Style::default().fg(MUTED_COLOR)), chunks[5]);
 }
 
 fn render_config_popup(f: &mut Frame<'_>, app: &mut App) {
-    let area = centered_rect(40, 20, f.area());
+    let overlays = app.overlay_choices();
+    let area = centered_rect(40, 20 + 3 * overlays.len().min(10) as u16, f.area());
     f.render_widget(Clear, area);
     let block = Block::default()
         .borders(Borders::ALL)
@@ -753,26 +790,71 @@ fn render_config_popup(f: &mut Frame<'_>, app: &mut App) {
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(3), // Option
-            Constraint::Min(1),
+            Constraint::Min(1),    // Options
             Constraint::Length(1), // Help
         ])
         .split(inner);
 
-    let check = if app.extract_project { "[x]" } else { "[ ]" };
-    let color = if app.extract_project { ACCENT_COLOR } else { TEXT_COLOR };
-    
-    let line = Line::from(vec![
-        Span::styled(check, Style::default().fg(color).add_modifier(Modifier::BOLD)),
-        Span::raw(" Extract project zip after download"),
-    ]);
+    let option = |checked: bool, label: String, focused: bool| {
+        let color = if checked { ACCENT_COLOR } else { TEXT_COLOR };
+        let style = if focused { Style::default().bg(Color::Rgb(40, 44, 52)) } else { Style::default() };
+        Line::from(vec![
+            Span::styled(if checked { "[x]" } else { "[ ]" }, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::raw(format!(" {}", label)),
+        ])
+        .style(style)
+    };
+
+    let mut lines = vec![
+        option(app.extract_project, "Extract project zip after download".to_string(), app.config_popup_idx == 0),
+        option(app.git_init, format!("Initialise git repository on {} (when extracting)", app.git_options.branch), app.config_popup_idx == 1),
+        Line::raw(""),
+    ];
+    if !overlays.is_empty() {
+        lines.push(Line::styled("Overlays (applied after extraction)", Style::default().fg(MUTED_COLOR)));
+    }
+    for (i, overlay) in overlays.iter().enumerate() {
+        lines.push(option(app.config.overlays.contains(overlay), overlay.clone(), app.config_popup_idx == i + 2));
+    }
 
-    f.render_widget(Paragraph::new(line), chunks[0]);
-    f.render_widget(Paragraph::new("Space/Enter: Toggle | Esc: Close").alignment(Alignment::Center).style(Style::default().fg(MUTED_COLOR)), chunks[2]);
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+    f.render_widget(Paragraph::new("↑↓: Move | Space/Enter: Toggle | Esc: Close").alignment(Alignment::Center).style(Style::default().fg(MUTED_COLOR)), chunks[1]);
+}
+
+fn render_paste_popup(f: &mut Frame<'_>, app: &mut App) {
+    let area = centered_rect(60, 20, f.area());
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().bg(BG_COLOR).fg(TEXT_COLOR))
+        .title(" Paste Share Link ");
+    f.render_widget(block.clone(), area);
+
+    let inner = block.inner(area);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2), // Label
+            Constraint::Min(2),    // Input
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    f.render_widget(Paragraph::new("start.spring.io link (https://start.spring.io/#!...):").style(Style::default().fg(ACCENT_COLOR)), chunks[0]);
+    f.render_widget(
+        Paragraph::new(format!("{}█", app.paste_buffer))
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::BOTTOM).border_style(Style::default().fg(ACCENT_COLOR))),
+        chunks[1],
+    );
+    f.render_widget(Paragraph::new("Enter: Load | Esc: Cancel").alignment(Alignment::Center).style(Style::default().fg(MUTED_COLOR)), chunks[2]);
 }
 
 fn render_message_popup(f: &mut Frame<'_>, app: &mut App) {
-    let area = centered_rect(50, 20, f.area());
+    // grow with hook output
+    let height = (20 + 3 * app.message_popup_text.lines().count().saturating_sub(2) as u16).min(80);
+    let area = centered_rect(50, height, f.area());
     f.render_widget(Clear, area);
     let title_color = if app.message_popup_is_error { Color::Red } else { ACCENT_COLOR };
     
@@ -828,7 +910,26 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 
 // --- RUN LOOP ---
 
-pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// Open the TUI with the capability defaults, overridden by the user's configured `defaults`
+pub async fn run(defaults: &GlobalConfig) -> Result<(), Box<dyn std::error::Error>> {
+    start(None, Some(defaults), None, HookCommands::from_settings(defaults)).await
+}
+
+/// Open the TUI prefilled with `config`, optionally greeting the user with a warning popup
+pub async fn run_with_config(
+    config: SprintInitConfig,
+    warning: Option<String>,
+    hooks: Option<HookCommands>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    start(Some(config), None, warning, hooks).await
+}
+
+async fn start(
+    config: Option<SprintInitConfig>,
+    defaults: Option<&GlobalConfig>,
+    warning: Option<String>,
+    hooks: Option<HookCommands>,
+) -> Result<(), Box<dyn std::error::Error>> {
     println!("Fetching Spring Initializr capabilities...");
     let capabilities = api::get_capabilities().await?;
     println!("Fetching available dependencies...");
@@ -837,15 +938,31 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = App::new(capabilities, dependencies);
+    app.hooks = hooks;
+    if let Some(config) = config {
+        app = app.with_config(config);
+    } else if let Some(defaults) = defaults {
+        let mut config = app.config.clone();
+        defaults.apply_to(&mut config);
+        app.load_config(config);
+        if let Some(dir) = &defaults.dir {
+            app.output_dir = dir.clone();
+        }
+        app.git_init = defaults.git_init.unwrap_or(false);
+        app.git_options = GitOptions::from_settings(defaults);
+    }
+    if let Some(warning) = warning {
+        app.show_message("Warning", warning, true);
+    }
     let res = run_app(&mut terminal, &mut app).await;
 
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste)?;
     terminal.show_cursor()?;
 
     if let Err(err) = res {
@@ -854,6 +971,7 @@ pub async fn run() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[allow(clippy::collapsible_if, clippy::collapsible_match)]
 async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<(), Box<dyn std::error::Error>>
 where
     <B as Backend>::Error: std::error::Error + Send + Sync + 'static,
@@ -861,7 +979,15 @@ where
     loop {
         terminal.draw(|f| ui(f, app))?;
 
-        if let Event::Key(key) = event::read()? {
+        let event = event::read()?;
+        if let Event::Paste(text) = &event {
+            if app.show_paste_popup {
+                app.paste_buffer.push_str(text.trim());
+            }
+            continue;
+        }
+
+        if let Event::Key(key) = event {
             if app.input_mode {
                 match key.code {
                     KeyCode::Enter => app.finish_edit(),
@@ -897,7 +1023,7 @@ where
                    }
                    KeyCode::Right | KeyCode::Char('l') => {
                        if !app.export_focus_filename {
-                           if app.export_format_idx < 2 { app.export_format_idx += 1; }
+                           if app.export_format_idx < EXPORT_FORMATS.len() - 1 { app.export_format_idx += 1; }
                        }
                    }
                    KeyCode::Char(c) => {
@@ -911,14 +1037,17 @@ where
                        }
                    }
                    KeyCode::Enter => {
-                       let fmt = match app.export_format_idx {
-                           0 => FileType::Yaml,
-                           1 => FileType::Json,
-                           _ => FileType::Toml,
-                       };
                        let name = if app.export_filename.is_empty() { None } else { Some(app.export_filename.clone()) };
-                       
-                       match generator::generate_project_config_file(&app.config, fmt, name) {
+                       let result = match app.export_format_idx {
+                           0 => generator::generate_project_config_file(&app.config, FileType::Yaml, name),
+                           1 => generator::generate_project_config_file(&app.config, FileType::Json, name),
+                           2 => generator::generate_project_config_file(&app.config, FileType::Toml, name),
+                           3 => generator::generate_command_file(&app.config, CommandStyle::Curl, name).map(|_| ()),
+                           4 => generator::generate_command_file(&app.config, CommandStyle::Httpie, name).map(|_| ()),
+                           _ => generator::generate_command_file(&app.config, CommandStyle::SpringInit, name).map(|_| ()),
+                       };
+
+                       match result {
                            Ok(_) => {
                                 app.status_message = "Config Exported!".to_string();
                                 app.show_message_popup = true;
@@ -938,12 +1067,34 @@ where
                    }
                    _ => {}
                 }
+            } else if let Some(editor) = &mut app.application_editor {
+                if !editor.handle_key(&mut app.config.application, key.code) {
+                    app.application_editor = None;
+                }
             } else if app.show_config_popup {
                 match key.code {
                     KeyCode::Esc | KeyCode::Char('C') => app.show_config_popup = false,
-                    KeyCode::Enter | KeyCode::Char(' ') => app.extract_project = !app.extract_project,
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        app.config_popup_idx = (app.config_popup_idx + 1).min(app.overlay_choices().len() + 1);
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => app.config_popup_idx = app.config_popup_idx.saturating_sub(1),
+                    KeyCode::Enter | KeyCode::Char(' ') if app.config_popup_idx == 0 => app.extract_project = !app.extract_project,
+                    KeyCode::Enter | KeyCode::Char(' ') if app.config_popup_idx == 1 => app.git_init = !app.git_init,
+                    KeyCode::Enter | KeyCode::Char(' ') => {
+                        if let Some(overlay) = app.overlay_choices().get(app.config_popup_idx - 2).cloned() {
+                            app.toggle_overlay(overlay);
+                        }
+                    }
                      _ => {}
                 }
+            } else if app.show_paste_popup {
+                match key.code {
+                    KeyCode::Esc => { app.show_paste_popup = false; app.paste_buffer.clear(); }
+                    KeyCode::Enter => app.apply_share_link(),
+                    KeyCode::Char(c) => app.paste_buffer.push(c),
+                    KeyCode::Backspace => { app.paste_buffer.pop(); }
+                    _ => {}
+                }
             } else if app.show_message_popup {
                 match key.code {
                     KeyCode::Esc | KeyCode::Enter | KeyCode::Char(' ') => app.show_message_popup = false,
@@ -953,6 +1104,13 @@ where
                 match key.code {
                     KeyCode::Char('q') | KeyCode::Char('Q') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
                     KeyCode::Char('C') => app.show_config_popup = true,
+                    // capital shortcuts only outside the dependency search, which takes them as text
+                    KeyCode::Char('A') if app.active_pane == ActivePane::Config => app.application_editor = Some(application::Editor::default()),
+                    KeyCode::Char('P') if app.active_pane == ActivePane::Config => app.show_paste_popup = true,
+                    KeyCode::Char('L') if app.active_pane == ActivePane::Config => {
+                        let link = share::share_link(&app.config, &api::util::get_base_url());
+                        app.show_message("Share Link", link, false);
+                    }
                     KeyCode::Tab => app.toggle_pane(),
                     KeyCode::Down | KeyCode::Char('j') => {
                         match app.active_pane {
@@ -975,12 +1133,24 @@ where
                         if app.current_field == Field::Generate && app.active_pane == ActivePane::Config {
                             app.status_message = "Generating...".to_string();
                             terminal.draw(|f| ui(f, app))?;
-                            match generator::generate_project(&app.config, app.extract_project).await {
-                                Ok(_) =>  {
+                            match generator::generate_project_into(
+                                &app.config,
+                                app.extract_project,
+                                std::path::Path::new(&app.output_dir),
+                                app.git_init.then_some(&app.git_options),
+                                app.hooks.as_ref(),
+                            )
+                            .await
+                            {
+                                Ok(generation) =>  {
                                     app.status_message = "Success! Saved.".to_string();
                                     app.show_message_popup = true;
                                     app.message_popup_title = "Success".to_string();
                                     app.message_popup_text = format!("Project '{}' generated successfully!", app.config.artifact_id);
+                                    let output = generation.report();
+                                    if !output.is_empty() {
+                                        app.message_popup_text.push_str(&format!("\n\n{}", output));
+                                    }
                                     app.message_popup_is_error = false;
                                 },
                                 Err(e) => {
@@ -0,0 +1,148 @@
+// reviewable diff view for the upgrade assistant: pick the hunks to apply, then confirm
+use super::{ACCENT_COLOR, BG_COLOR, MUTED_COLOR, TEXT_COLOR};
+use crate::project::upgrade::UpgradePlan;
+use crossterm::{
+    event::{self, Event, KeyCode},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+use std::io;
+
+struct Review<'a> {
+    plan: &'a UpgradePlan,
+    selected: Vec<bool>,
+    list_state: ListState,
+    scroll: u16,
+}
+
+impl Review<'_> {
+    fn current(&self) -> usize {
+        self.list_state.selected().unwrap_or(0)
+    }
+
+    fn select(&mut self, index: usize) {
+        self.list_state.select(Some(index));
+        self.scroll = 0;
+    }
+}
+
+fn ui(f: &mut Frame<'_>, review: &mut Review) {
+    let area = f.area();
+    f.render_widget(Block::default().style(Style::default().bg(BG_COLOR)), area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[0]);
+
+    let items: Vec<ListItem> = review
+        .plan
+        .hunks
+        .iter()
+        .zip(&review.selected)
+        .map(|(hunk, selected)| {
+            let mark = if *selected { "[x]" } else { "[ ]" };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", mark), Style::default().fg(ACCENT_COLOR)),
+                Span::styled(hunk.file.clone(), Style::default().fg(TEXT_COLOR)),
+                Span::styled(format!(" {}", hunk.header), Style::default().fg(MUTED_COLOR)),
+            ]))
+        })
+        .collect();
+    let title = format!(" Spring Boot {} -> {} ", review.plan.from, review.plan.to);
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded).title(title))
+        .highlight_style(Style::default().bg(Color::Rgb(40, 44, 52)).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, columns[0], &mut review.list_state);
+
+    let hunk = &review.plan.hunks[review.current()];
+    let lines: Vec<Line> = hunk
+        .lines
+        .iter()
+        .map(|l| {
+            let color = match l.chars().next() {
+                Some('+') => Color::Green,
+                Some('-') => Color::Red,
+                _ => TEXT_COLOR,
+            };
+            Line::from(Span::styled(l.clone(), Style::default().fg(color)))
+        })
+        .collect();
+    let diff = Paragraph::new(lines).scroll((review.scroll, 0)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(format!(" {} ", hunk.file)),
+    );
+    f.render_widget(diff, columns[1]);
+
+    let count = review.selected.iter().filter(|s| **s).count();
+    let footer = format!(
+        " <Space> toggle  <a> toggle all  <PgUp/PgDn> scroll  <Enter> apply {} hunks  <Esc> cancel",
+        count
+    );
+    f.render_widget(Paragraph::new(footer).style(Style::default().fg(MUTED_COLOR)), rows[1]);
+}
+
+/// Let the user pick hunks of `plan`; None when the review was cancelled
+pub fn review(plan: &UpgradePlan) -> Result<Option<Vec<usize>>, Box<dyn std::error::Error>> {
+    if plan.hunks.is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let mut review = Review { plan, selected: vec![true; plan.hunks.len()], list_state: ListState::default(), scroll: 0 };
+    review.select(0);
+
+    let result = loop {
+        if let Err(e) = terminal.draw(|f| ui(f, &mut review)) {
+            break Err(e.into());
+        }
+        let key = match event::read() {
+            Ok(Event::Key(key)) => key,
+            Ok(_) => continue,
+            Err(e) => break Err(e.into()),
+        };
+        let len = plan.hunks.len();
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => review.select((review.current() + 1) % len),
+            KeyCode::Up | KeyCode::Char('k') => review.select((review.current() + len - 1) % len),
+            KeyCode::PageDown => review.scroll = review.scroll.saturating_add(10),
+            KeyCode::PageUp => review.scroll = review.scroll.saturating_sub(10),
+            KeyCode::Char(' ') => {
+                let i = review.current();
+                review.selected[i] = !review.selected[i];
+            }
+            KeyCode::Char('a') => {
+                let all = review.selected.iter().all(|s| *s);
+                review.selected.iter_mut().for_each(|s| *s = !all);
+            }
+            KeyCode::Enter => {
+                break Ok(Some((0..len).filter(|&i| review.selected[i]).collect()));
+            }
+            KeyCode::Esc | KeyCode::Char('q') => break Ok(None),
+            _ => {}
+        }
+    };
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
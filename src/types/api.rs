@@ -7,6 +7,31 @@ use std::collections::HashMap;
 pub struct InitializrDependencies {
     pub boot_version: String,
     pub dependencies: HashMap<String, Dependency>,
+    /// BOMs referenced by the dependencies' `bom`, by id
+    #[serde(default)]
+    pub boms: HashMap<String, Bom>,
+    /// repositories referenced by dependencies and BOMs, by id
+    #[serde(default)]
+    pub repositories: HashMap<String, Repository>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bom {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    #[serde(default)]
+    pub repositories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Repository {
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub snapshot_enabled: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -17,6 +42,11 @@ pub struct Dependency {
     pub artifact_id: String,
     pub scope: String,
     pub bom: Option<String>,
+    /// only set when the version is not managed by Spring Boot
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub repository: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
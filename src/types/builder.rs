@@ -0,0 +1,350 @@
+// typed construction of SprintInitConfig for library users: starts from the defaults an Initializr
+// instance advertises (SprintInitConfig::from_capabilities) and checks the result against the
+// same capabilities, so a config that build() accepts is one that instance can generate.
+//
+//     let config = SprintInitConfig::builder(&capabilities)
+//         .project_type(ProjectType::GradleKotlin)
+//         .language(Language::Kotlin)
+//         .group_id("org.acme")
+//         .artifact_id("orders")
+//         .dependency("web")
+//         .build()?;
+use crate::types::api::{CapabilityGroup, InitializrCapabilities};
+use crate::types::generic::{normalize_dependencies, ApplicationSettings, SprintInitConfig};
+use std::fmt;
+
+/// Initializr project type, `type` in the capabilities
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectType {
+    Maven,
+    Gradle,
+    GradleKotlin,
+    /// any other type a custom Initializr offers
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Language {
+    Java,
+    Kotlin,
+    Groovy,
+    Other(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packaging {
+    Jar,
+    War,
+    Other(String),
+}
+
+impl ProjectType {
+    pub fn id(&self) -> &str {
+        match self {
+            ProjectType::Maven => "maven-project",
+            ProjectType::Gradle => "gradle-project",
+            ProjectType::GradleKotlin => "gradle-project-kotlin",
+            ProjectType::Other(id) => id,
+        }
+    }
+}
+
+impl Language {
+    pub fn id(&self) -> &str {
+        match self {
+            Language::Java => "java",
+            Language::Kotlin => "kotlin",
+            Language::Groovy => "groovy",
+            Language::Other(id) => id,
+        }
+    }
+}
+
+impl Packaging {
+    pub fn id(&self) -> &str {
+        match self {
+            Packaging::Jar => "jar",
+            Packaging::War => "war",
+            Packaging::Other(id) => id,
+        }
+    }
+}
+
+impl From<&str> for ProjectType {
+    fn from(id: &str) -> Self {
+        match id {
+            "maven-project" => ProjectType::Maven,
+            "gradle-project" => ProjectType::Gradle,
+            "gradle-project-kotlin" => ProjectType::GradleKotlin,
+            other => ProjectType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<&str> for Language {
+    fn from(id: &str) -> Self {
+        match id {
+            "java" => Language::Java,
+            "kotlin" => Language::Kotlin,
+            "groovy" => Language::Groovy,
+            other => Language::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<&str> for Packaging {
+    fn from(id: &str) -> Self {
+        match id {
+            "jar" => Packaging::Jar,
+            "war" => Packaging::War,
+            other => Packaging::Other(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ProjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+impl fmt::Display for Packaging {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// Why build() refused a config, `field` is the config key
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum BuildError {
+    #[error("{field} '{value}' is not offered by this Initializr, expected one of: {}", allowed.join(", "))]
+    Unsupported { field: &'static str, value: String, allowed: Vec<String> },
+    #[error("{field} must not be empty")]
+    Empty { field: &'static str },
+    #[error("{field} '{value}' is not a valid Java package name")]
+    InvalidPackage { field: &'static str, value: String },
+    #[error("configurationFileFormat must be 'properties' or 'yaml', got '{0}'")]
+    ConfigurationFileFormat(String),
+}
+
+pub struct ConfigBuilder<'a> {
+    capabilities: &'a InitializrCapabilities,
+    config: SprintInitConfig,
+    /// package name set explicitly, otherwise derived from groupId and artifactId like the tui does
+    package_name: Option<String>,
+}
+
+impl SprintInitConfig {
+    /// Builder starting from the defaults `capabilities` advertises
+    pub fn builder(capabilities: &InitializrCapabilities) -> ConfigBuilder<'_> {
+        ConfigBuilder { capabilities, config: SprintInitConfig::from_capabilities(capabilities), package_name: None }
+    }
+}
+
+impl ConfigBuilder<'_> {
+    pub fn project_type(mut self, project_type: ProjectType) -> Self {
+        self.config.project_type = project_type.id().to_string();
+        self
+    }
+
+    pub fn language(mut self, language: Language) -> Self {
+        self.config.language = language.id().to_string();
+        self
+    }
+
+    pub fn packaging(mut self, packaging: Packaging) -> Self {
+        self.config.packaging = packaging.id().to_string();
+        self
+    }
+
+    pub fn java_version(mut self, java_version: i32) -> Self {
+        self.config.java_version = java_version;
+        self
+    }
+
+    pub fn boot_version(mut self, boot_version: impl Into<String>) -> Self {
+        self.config.boot_version = boot_version.into();
+        self
+    }
+
+    /// `properties` or `yaml`
+    pub fn configuration_file_format(mut self, format: impl Into<String>) -> Self {
+        self.config.configuration_file_format = format.into();
+        self
+    }
+
+    pub fn group_id(mut self, group_id: impl Into<String>) -> Self {
+        self.config.group_id = group_id.into();
+        self
+    }
+
+    pub fn artifact_id(mut self, artifact_id: impl Into<String>) -> Self {
+        self.config.artifact_id = artifact_id.into();
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.config.name = name.into();
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.config.description = description.into();
+        self
+    }
+
+    /// Defaults to groupId.artifactId
+    pub fn package_name(mut self, package_name: impl Into<String>) -> Self {
+        self.package_name = Some(package_name.into());
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.config.version = version.into();
+        self
+    }
+
+    /// Add a dependency id; ids are not checked, the capabilities don't list them
+    pub fn dependency(mut self, id: &str) -> Self {
+        self.config.dependencies = normalize_dependencies(&format!("{},{}", self.config.dependencies, id));
+        self
+    }
+
+    pub fn dependencies<'i>(self, ids: impl IntoIterator<Item = &'i str>) -> Self {
+        ids.into_iter().fold(self, |builder, id| builder.dependency(id))
+    }
+
+    pub fn overlay(mut self, name: impl Into<String>) -> Self {
+        self.config.overlays.push(name.into());
+        self
+    }
+
+    pub fn application(mut self, application: ApplicationSettings) -> Self {
+        self.config.application = application;
+        self
+    }
+
+    /// The config, checked against the capabilities the builder started from
+    pub fn build(self) -> Result<SprintInitConfig, BuildError> {
+        let ConfigBuilder { capabilities, mut config, package_name } = self;
+        config.package_name =
+            package_name.unwrap_or_else(|| format!("{}.{}", config.group_id, config.artifact_id).replace("-", ""));
+
+        for (field, value) in [
+            ("groupId", &config.group_id),
+            ("artifactId", &config.artifact_id),
+            ("name", &config.name),
+            ("packageName", &config.package_name),
+            ("version", &config.version),
+        ] {
+            if value.trim().is_empty() {
+                return Err(BuildError::Empty { field });
+            }
+        }
+        if !is_package_name(&config.package_name) {
+            return Err(BuildError::InvalidPackage { field: "packageName", value: config.package_name });
+        }
+
+        offered("type", &config.project_type, capabilities.project_type.as_ref())?;
+        offered("language", &config.language, capabilities.language.as_ref())?;
+        offered("packaging", &config.packaging, capabilities.packaging.as_ref())?;
+        offered("javaVersion", &config.java_version.to_string(), capabilities.java_version.as_ref())?;
+        offered("bootVersion", &config.boot_version, capabilities.boot_version.as_ref())?;
+        if !matches!(config.configuration_file_format.as_str(), "properties" | "yaml") {
+            return Err(BuildError::ConfigurationFileFormat(config.configuration_file_format));
+        }
+        Ok(config)
+    }
+}
+
+/// `value` is one of the group's values; anything goes when the instance doesn't advertise the group
+fn offered(field: &'static str, value: &str, group: Option<&CapabilityGroup>) -> Result<(), BuildError> {
+    match group {
+        Some(group) if !group.values.iter().any(|v| v.id == value) => Err(BuildError::Unsupported {
+            field,
+            value: value.to_string(),
+            allowed: group.values.iter().map(|v| v.id.clone()).collect(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn is_package_name(name: &str) -> bool {
+    name.split('.').all(|part| {
+        let mut chars = part.chars();
+        chars.next().is_some_and(|c| c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities() -> InitializrCapabilities {
+        serde_json::from_str(
+            r#"{
+                "_links": {},
+                "type": { "type": "action", "default": "maven-project", "values": [
+                    { "id": "maven-project", "name": "Maven" },
+                    { "id": "gradle-project-kotlin", "name": "Gradle - Kotlin" },
+                    { "id": "bazel-project", "name": "Bazel" }
+                ] },
+                "language": { "type": "single-select", "default": "java", "values": [
+                    { "id": "java", "name": "Java" }, { "id": "kotlin", "name": "Kotlin" }
+                ] },
+                "bootVersion": { "type": "single-select", "default": "3.5.10", "values": [
+                    { "id": "4.0.2", "name": "4.0.2" }, { "id": "3.5.10", "name": "3.5.10" }
+                ] },
+                "groupId": { "type": "text", "default": "com.example" },
+                "artifactId": { "type": "text", "default": "demo" }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn builds_from_capability_defaults() {
+        let capabilities = capabilities();
+
+        let config = SprintInitConfig::builder(&capabilities)
+            .project_type(ProjectType::Other("bazel-project".to_string()))
+            .language(Language::Kotlin)
+            .group_id("org.acme")
+            .artifact_id("order-service")
+            .dependencies(["web", "security", "web"])
+            .build()
+            .unwrap();
+
+        assert_eq!(config.project_type, "bazel-project");
+        assert_eq!(config.boot_version, "3.5.10");
+        assert_eq!(config.package_name, "org.acme.orderservice");
+        assert_eq!(config.dependencies, "web,security");
+        assert_eq!(ProjectType::from("gradle-project-kotlin"), ProjectType::GradleKotlin);
+    }
+
+    #[test]
+    fn rejects_what_the_instance_does_not_offer() {
+        let capabilities = capabilities();
+
+        let unsupported = SprintInitConfig::builder(&capabilities).language(Language::Groovy).build();
+        let empty = SprintInitConfig::builder(&capabilities).artifact_id("").build();
+        let package = SprintInitConfig::builder(&capabilities).package_name("org.acme.1orders").build();
+
+        assert_eq!(
+            unsupported,
+            Err(BuildError::Unsupported {
+                field: "language",
+                value: "groovy".to_string(),
+                allowed: vec!["java".to_string(), "kotlin".to_string()],
+            })
+        );
+        assert_eq!(empty, Err(BuildError::Empty { field: "artifactId" }));
+        assert!(matches!(package, Err(BuildError::InvalidPackage { .. })));
+    }
+}
@@ -2,19 +2,445 @@ use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+use crate::api::snapshot::Backend;
+use crate::types::generic::{normalize_dependencies, ApplicationSettings, SprintInitConfig};
+
+/// Defaults for new projects. The same keys are read from the global config,
+/// from `.spring-tui.*` files in the working directory and its parents, and from cli flags.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
 pub struct GlobalConfig {
-    pub dir: String,
+    /// directory generated projects are written to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packaging: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration_file_format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub java_version: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    /// package prefix, the artifact id is appended to get the package name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_base: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// comma separated dependency ids, preselected in the tui
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependencies: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artifact_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// full package name, wins over packageBase
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_name: Option<String>,
+    /// Initializr instance, https://start.spring.io/ when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// comma separated template overlays, names in the overlays dir or paths
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overlays: Option<String>,
+    /// comma separated directories with user recipes, next to <config dir>/recipes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipe_dirs: Option<String>,
+    /// shell commands run around each generation, see generator::hooks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_request_hook: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_download_hook: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_extract_hook: Option<String>,
+    /// run the git step after extraction, see generator::git
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_init: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    /// commit message template, e.g. "Generate {{artifactId}}"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_commit_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_remote: Option<String>,
+    /// write .spring-tui.yaml into extracted projects, on when unset, see generator::provenance
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<bool>,
+    /// initializr, offline or auto (offline when Initializr is unreachable), see api::snapshot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    /// Initializr metadata snapshot used offline, <cache dir>/snapshot when unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_dir: Option<String>,
+    /// application settings, written to the file by hand or read from a project's provenance;
+    /// there is no `config set` key for them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application: Option<ApplicationSettings>,
+}
+
+impl GlobalConfig {
+    /// Settings that reproduce `config` when applied with apply_to
+    pub fn from_project(config: &SprintInitConfig) -> Self {
+        GlobalConfig {
+            project_type: Some(config.project_type.clone()),
+            language: Some(config.language.clone()),
+            packaging: Some(config.packaging.clone()),
+            configuration_file_format: Some(config.configuration_file_format.clone()),
+            java_version: Some(config.java_version),
+            boot_version: Some(config.boot_version.clone()),
+            group_id: Some(config.group_id.clone()),
+            version: Some(config.version.clone()),
+            dependencies: Some(config.dependencies.clone()),
+            artifact_id: Some(config.artifact_id.clone()),
+            name: Some(config.name.clone()),
+            description: Some(config.description.clone()),
+            package_name: Some(config.package_name.clone()),
+            overlays: (!config.overlays.is_empty()).then(|| config.overlays.join(",")),
+            application: (!config.application.is_empty()).then(|| config.application.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// Overwrite the fields of `config` that have a default here
+    pub fn apply_to(&self, config: &mut SprintInitConfig) {
+        let set = |target: &mut String, value: &Option<String>| {
+            if let Some(v) = value {
+                *target = v.clone();
+            }
+        };
+        set(&mut config.project_type, &self.project_type);
+        set(&mut config.language, &self.language);
+        set(&mut config.packaging, &self.packaging);
+        set(&mut config.configuration_file_format, &self.configuration_file_format);
+        set(&mut config.boot_version, &self.boot_version);
+        set(&mut config.group_id, &self.group_id);
+        set(&mut config.version, &self.version);
+        set(&mut config.dependencies, &self.dependencies);
+        set(&mut config.artifact_id, &self.artifact_id);
+        set(&mut config.name, &self.name);
+        set(&mut config.description, &self.description);
+        if let Some(v) = self.java_version {
+            config.java_version = v;
+        }
+        if let Some(overlays) = &self.overlays {
+            config.overlays = overlays.split(',').map(str::trim).filter(|o| !o.is_empty()).map(String::from).collect();
+        }
+        if let Some(application) = &self.application {
+            config.application = application.clone();
+        }
+
+        let base = self.package_base.as_ref().or(self.group_id.as_ref());
+        if let Some(package_name) = &self.package_name {
+            config.package_name = package_name.clone();
+        } else if let Some(base) = base {
+            config.package_name = format!("{}.{}", base, config.artifact_id).replace("-", "");
+        }
+    }
+}
+
+/// Keys of GlobalConfig, as accepted by `config get/set/unset`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKey {
+    Dir,
+    ProjectType,
+    Language,
+    Packaging,
+    ConfigurationFileFormat,
+    JavaVersion,
+    BootVersion,
+    GroupId,
+    PackageBase,
+    Version,
+    Dependencies,
+    ArtifactId,
+    Name,
+    Description,
+    PackageName,
+    BaseUrl,
+    Overlays,
+    RecipeDirs,
+    BeforeRequestHook,
+    AfterDownloadHook,
+    AfterExtractHook,
+    GitInit,
+    GitBranch,
+    GitCommitMessage,
+    GitRemote,
+    Provenance,
+    Backend,
+    SnapshotDir,
+}
+
+impl ConfigKey {
+    pub const ALL: [ConfigKey; 28] = [
+        ConfigKey::Dir,
+        ConfigKey::ProjectType,
+        ConfigKey::Language,
+        ConfigKey::Packaging,
+        ConfigKey::ConfigurationFileFormat,
+        ConfigKey::JavaVersion,
+        ConfigKey::BootVersion,
+        ConfigKey::GroupId,
+        ConfigKey::PackageBase,
+        ConfigKey::Version,
+        ConfigKey::Dependencies,
+        ConfigKey::ArtifactId,
+        ConfigKey::Name,
+        ConfigKey::Description,
+        ConfigKey::PackageName,
+        ConfigKey::BaseUrl,
+        ConfigKey::Overlays,
+        ConfigKey::RecipeDirs,
+        ConfigKey::BeforeRequestHook,
+        ConfigKey::AfterDownloadHook,
+        ConfigKey::AfterExtractHook,
+        ConfigKey::GitInit,
+        ConfigKey::GitBranch,
+        ConfigKey::GitCommitMessage,
+        ConfigKey::GitRemote,
+        ConfigKey::Provenance,
+        ConfigKey::Backend,
+        ConfigKey::SnapshotDir,
+    ];
+
+    /// Key name as written in config files
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConfigKey::Dir => "dir",
+            ConfigKey::ProjectType => "projectType",
+            ConfigKey::Language => "language",
+            ConfigKey::Packaging => "packaging",
+            ConfigKey::ConfigurationFileFormat => "configurationFileFormat",
+            ConfigKey::JavaVersion => "javaVersion",
+            ConfigKey::BootVersion => "bootVersion",
+            ConfigKey::GroupId => "groupId",
+            ConfigKey::PackageBase => "packageBase",
+            ConfigKey::Version => "version",
+            ConfigKey::Dependencies => "dependencies",
+            ConfigKey::ArtifactId => "artifactId",
+            ConfigKey::Name => "name",
+            ConfigKey::Description => "description",
+            ConfigKey::PackageName => "packageName",
+            ConfigKey::BaseUrl => "baseUrl",
+            ConfigKey::Overlays => "overlays",
+            ConfigKey::RecipeDirs => "recipeDirs",
+            ConfigKey::BeforeRequestHook => "beforeRequestHook",
+            ConfigKey::AfterDownloadHook => "afterDownloadHook",
+            ConfigKey::AfterExtractHook => "afterExtractHook",
+            ConfigKey::GitInit => "gitInit",
+            ConfigKey::GitBranch => "gitBranch",
+            ConfigKey::GitCommitMessage => "gitCommitMessage",
+            ConfigKey::GitRemote => "gitRemote",
+            ConfigKey::Provenance => "provenance",
+            ConfigKey::Backend => "backend",
+            ConfigKey::SnapshotDir => "snapshotDir",
+        }
+    }
+
+    /// Environment variable overriding this key, e.g. SPRING_TUI_GROUP_ID
+    pub fn env_var(&self) -> String {
+        let mut var = String::from("SPRING_TUI_");
+        for c in self.name().chars() {
+            if c.is_ascii_uppercase() {
+                var.push('_');
+            }
+            var.push(c.to_ascii_uppercase());
+        }
+        var
+    }
+
+    fn field<'a>(&self, config: &'a mut GlobalConfig) -> Option<&'a mut Option<String>> {
+        match self {
+            ConfigKey::Dir => Some(&mut config.dir),
+            ConfigKey::ProjectType => Some(&mut config.project_type),
+            ConfigKey::Language => Some(&mut config.language),
+            ConfigKey::Packaging => Some(&mut config.packaging),
+            ConfigKey::ConfigurationFileFormat => Some(&mut config.configuration_file_format),
+            ConfigKey::JavaVersion => None,
+            ConfigKey::BootVersion => Some(&mut config.boot_version),
+            ConfigKey::GroupId => Some(&mut config.group_id),
+            ConfigKey::PackageBase => Some(&mut config.package_base),
+            ConfigKey::Version => Some(&mut config.version),
+            ConfigKey::Dependencies => Some(&mut config.dependencies),
+            ConfigKey::ArtifactId => Some(&mut config.artifact_id),
+            ConfigKey::Name => Some(&mut config.name),
+            ConfigKey::Description => Some(&mut config.description),
+            ConfigKey::PackageName => Some(&mut config.package_name),
+            ConfigKey::BaseUrl => Some(&mut config.base_url),
+            ConfigKey::Overlays => Some(&mut config.overlays),
+            ConfigKey::RecipeDirs => Some(&mut config.recipe_dirs),
+            ConfigKey::BeforeRequestHook => Some(&mut config.before_request_hook),
+            ConfigKey::AfterDownloadHook => Some(&mut config.after_download_hook),
+            ConfigKey::AfterExtractHook => Some(&mut config.after_extract_hook),
+            ConfigKey::GitInit => None,
+            ConfigKey::GitBranch => Some(&mut config.git_branch),
+            ConfigKey::GitCommitMessage => Some(&mut config.git_commit_message),
+            ConfigKey::GitRemote => Some(&mut config.git_remote),
+            ConfigKey::Provenance => None,
+            ConfigKey::Backend => Some(&mut config.backend),
+            ConfigKey::SnapshotDir => Some(&mut config.snapshot_dir),
+        }
+    }
+
+    pub fn get(&self, config: &GlobalConfig) -> Option<String> {
+        let value = match self {
+            ConfigKey::Dir => &config.dir,
+            ConfigKey::ProjectType => &config.project_type,
+            ConfigKey::Language => &config.language,
+            ConfigKey::Packaging => &config.packaging,
+            ConfigKey::ConfigurationFileFormat => &config.configuration_file_format,
+            ConfigKey::JavaVersion => return config.java_version.map(|v| v.to_string()),
+            ConfigKey::BootVersion => &config.boot_version,
+            ConfigKey::GroupId => &config.group_id,
+            ConfigKey::PackageBase => &config.package_base,
+            ConfigKey::Version => &config.version,
+            ConfigKey::Dependencies => &config.dependencies,
+            ConfigKey::ArtifactId => &config.artifact_id,
+            ConfigKey::Name => &config.name,
+            ConfigKey::Description => &config.description,
+            ConfigKey::PackageName => &config.package_name,
+            ConfigKey::BaseUrl => &config.base_url,
+            ConfigKey::Overlays => &config.overlays,
+            ConfigKey::RecipeDirs => &config.recipe_dirs,
+            ConfigKey::BeforeRequestHook => &config.before_request_hook,
+            ConfigKey::AfterDownloadHook => &config.after_download_hook,
+            ConfigKey::AfterExtractHook => &config.after_extract_hook,
+            ConfigKey::GitInit => return config.git_init.map(|v| v.to_string()),
+            ConfigKey::GitBranch => &config.git_branch,
+            ConfigKey::GitCommitMessage => &config.git_commit_message,
+            ConfigKey::GitRemote => &config.git_remote,
+            ConfigKey::Provenance => return config.provenance.map(|v| v.to_string()),
+            ConfigKey::Backend => &config.backend,
+            ConfigKey::SnapshotDir => &config.snapshot_dir,
+        };
+        value.clone()
+    }
+
+    /// Parse and validate `value` for this key, then store it
+    pub fn set(&self, config: &mut GlobalConfig, value: &str) -> anyhow::Result<()> {
+        let value = value.trim();
+        match self {
+            ConfigKey::JavaVersion => {
+                config.java_version = Some(
+                    value
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("javaVersion must be a number, got '{}'", value))?,
+                );
+                return Ok(());
+            }
+            ConfigKey::GitInit => {
+                config.git_init = Some(
+                    value
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("gitInit must be true or false, got '{}'", value))?,
+                );
+                return Ok(());
+            }
+            ConfigKey::Provenance => {
+                config.provenance = Some(
+                    value
+                        .parse()
+                        .map_err(|_| anyhow::anyhow!("provenance must be true or false, got '{}'", value))?,
+                );
+                return Ok(());
+            }
+            ConfigKey::ConfigurationFileFormat if value != "properties" && value != "yaml" => {
+                anyhow::bail!("configurationFileFormat must be 'properties' or 'yaml', got '{}'", value)
+            }
+            ConfigKey::Backend => {
+                value.parse::<Backend>().map_err(anyhow::Error::msg)?;
+            }
+            ConfigKey::BaseUrl if !value.starts_with("http://") && !value.starts_with("https://") => {
+                anyhow::bail!("baseUrl must be an http(s) url, got '{}'", value)
+            }
+            _ if value.is_empty() => anyhow::bail!("{} cannot be empty, use unset to remove it", self.name()),
+            _ => {}
+        }
+
+        let value = match self {
+            ConfigKey::Dependencies | ConfigKey::Overlays => normalize_dependencies(value),
+            _ => value.to_string(),
+        };
+        if let Some(field) = self.field(config) {
+            *field = Some(value);
+        }
+        Ok(())
+    }
+
+    pub fn unset(&self, config: &mut GlobalConfig) {
+        match self {
+            ConfigKey::JavaVersion => config.java_version = None,
+            ConfigKey::GitInit => config.git_init = None,
+            ConfigKey::Provenance => config.provenance = None,
+            _ => {
+                if let Some(field) = self.field(config) {
+                    *field = None;
+                }
+            }
+        }
+    }
 }
 
-impl GlobalConfig {}
+impl std::str::FromStr for ConfigKey {
+    type Err = anyhow::Error;
+
+    /// Accepts the file name (groupId) as well as the flag spelling (group-id)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.replace(['-', '_'], "").to_lowercase();
+        ConfigKey::ALL
+            .into_iter()
+            .find(|k| k.name().to_lowercase() == normalized)
+            .ok_or_else(|| {
+                let names: Vec<&str> = ConfigKey::ALL.iter().map(|k| k.name()).collect();
+                anyhow::anyhow!("Unknown config key '{}', expected one of: {}", s, names.join(", "))
+            })
+    }
+}
 
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
 pub enum FileType {
     Yaml,
     Json,
     Toml,
 }
 
+impl FileType {
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "yaml" | "yml" => Some(FileType::Yaml),
+            "json" => Some(FileType::Json),
+            "toml" => Some(FileType::Toml),
+            _ => None,
+        }
+    }
+
+    /// Guess the format of a config document: a JSON object, TOML tables / `key = value`, otherwise YAML
+    pub fn sniff(content: &str) -> Self {
+        if content.trim_start().starts_with('{') {
+            return FileType::Json;
+        }
+        let first = content
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty() && !l.starts_with('#'));
+        let is_toml = first.is_some_and(|line| {
+            line.starts_with('[')
+                || line.split_once('=').is_some_and(|(key, _)| {
+                    let key = key.trim().trim_matches('"');
+                    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || "_-.".contains(c))
+                })
+        });
+        if is_toml { FileType::Toml } else { FileType::Yaml }
+    }
+}
+
 // will add file path later
 pub struct ConfigMetadata {
     pub file_type: FileType,
@@ -27,15 +453,86 @@ impl ConfigMetadata {
             .and_then(|s| s.to_str())
             .ok_or_else(|| anyhow::anyhow!("No file extension found"))?;
 
-        let current_file_type = match extension {
-            "yaml" | "yml" => FileType::Yaml,
-            "json" => FileType::Json,
-            "toml" => FileType::Toml,
-            _ => anyhow::bail!("Unsuppored file type"),
-        };
+        let current_file_type = FileType::from_extension(extension)
+            .ok_or_else(|| anyhow::anyhow!("Unsuppored file type"))?;
 
         Ok(Self {
             file_type: current_file_type,
         })
     }
+
+    /// Format of a config read from `file_path`: `format` when given, then the extension,
+    /// then whatever the content looks like
+    pub fn detect(file_path: &str, content: &str, format: Option<FileType>) -> Self {
+        let file_type = format
+            .or_else(|| {
+                Path::new(file_path)
+                    .extension()
+                    .and_then(|s| s.to_str())
+                    .and_then(FileType::from_extension)
+            })
+            .unwrap_or_else(|| FileType::sniff(content));
+
+        Self { file_type }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConfigKey, ConfigMetadata, FileType, GlobalConfig};
+    use crate::types::generic::SprintInitConfig;
+
+    #[test]
+    fn sniffs_format_from_content() {
+        assert_eq!(FileType::sniff("  {\"groupId\": \"com.acme\"}"), FileType::Json);
+        assert_eq!(FileType::sniff("# generated\ngroupId = \"com.acme\"\n"), FileType::Toml);
+        assert_eq!(FileType::sniff("[project]\nname = \"x\"\n"), FileType::Toml);
+        assert_eq!(FileType::sniff("---\ngroupId: com.acme\n"), FileType::Yaml);
+        assert_eq!(FileType::sniff("description: a = b\n"), FileType::Yaml);
+    }
+
+    #[test]
+    fn explicit_format_wins_over_extension() {
+        let detected = ConfigMetadata::detect("config.yaml", "{}", Some(FileType::Toml));
+        assert_eq!(detected.file_type, FileType::Toml);
+        assert_eq!(ConfigMetadata::detect("config.conf", "{}", None).file_type, FileType::Json);
+        assert_eq!(ConfigMetadata::detect("-", "a: b", None).file_type, FileType::Yaml);
+    }
+
+    #[test]
+    fn config_keys_accept_file_and_flag_spelling() {
+        assert_eq!("groupId".parse::<ConfigKey>().unwrap(), ConfigKey::GroupId);
+        assert_eq!("group-id".parse::<ConfigKey>().unwrap(), ConfigKey::GroupId);
+        assert!("groupid.x".parse::<ConfigKey>().is_err());
+        assert_eq!(ConfigKey::GroupId.env_var(), "SPRING_TUI_GROUP_ID");
+        assert_eq!(ConfigKey::BaseUrl.env_var(), "SPRING_TUI_BASE_URL");
+    }
+
+    #[test]
+    fn config_keys_validate_and_normalise_values() {
+        let mut config = GlobalConfig::default();
+
+        ConfigKey::JavaVersion.set(&mut config, "21").unwrap();
+        ConfigKey::Dependencies.set(&mut config, "web, lombok,web").unwrap();
+
+        assert_eq!(config.java_version, Some(21));
+        assert_eq!(config.dependencies.as_deref(), Some("web,lombok"));
+        assert!(ConfigKey::JavaVersion.set(&mut config, "latest").is_err());
+        assert!(ConfigKey::ConfigurationFileFormat.set(&mut config, "xml").is_err());
+
+        ConfigKey::JavaVersion.unset(&mut config);
+        assert_eq!(ConfigKey::JavaVersion.get(&config), None);
+    }
+
+    #[test]
+    fn project_settings_reproduce_the_project() {
+        let mut project = SprintInitConfig { artifact_id: "orders".to_string(), overlays: vec!["service".to_string()], ..Default::default() };
+        project.application.server_port = Some(8081);
+        project.application.active_profiles = vec!["local".to_string()];
+
+        let mut reproduced = SprintInitConfig::default();
+        GlobalConfig::from_project(&project).apply_to(&mut reproduced);
+
+        assert_eq!(reproduced, project);
+    }
 }
@@ -1,20 +1,25 @@
 
 use serde::{Serialize,Deserialize}; 
+use std::collections::BTreeMap;
+use crate::types::api::InitializrCapabilities;
 
-#[derive(Serialize, Deserialize,Debug)]
+/// Format version written as `configVersion`; older files are migrated on load
+pub const CONFIG_VERSION: u32 = 2;
+
+#[derive(Serialize, Deserialize,Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SprintInitConfig {
-    /// gradle-kotline, gradle-groovy , gradle-mavn
+    /// config file format version, see config::migrate
+    pub config_version: u32,
+    /// maven-project, gradle-project, gradle-project-kotlin
     pub project_type: String,
-    /// java, kotlin, grovvyy
+    /// java, kotlin, groovy
     pub language: String,
-    /// 5.0.5
-    pub platform_version: String,
-    /// packageing : jar / war
+    /// packaging : jar / war
     pub packaging: String,
-    ///configurationFileFormat: properties/yaml
+    /// configurationFileFormat: properties / yaml
     pub configuration_file_format: String,
-    /// 25,21,17
+    /// 25, 21, 17
     pub java_version: i32,
     /// com.example
     pub group_id: String,
@@ -22,18 +27,173 @@ pub struct SprintInitConfig {
     pub artifact_id: String,
     /// demo
     pub name: String,
-    ///=Demo%20project%20for%20Spring%20Boot
+    /// Demo project for Spring Boot
     pub description: String,
-    /// com.exmaple.demo = groupId + name
+    /// com.example.demo = groupId + artifactId
     pub package_name: String,
-    ///lombok,devtools
+    /// lombok,devtools
     pub dependencies: String,
-    /// boot version : 2.4.5 SNAPSHOT
+    /// boot version : 3.5.10, empty for the default of the Initializr instance
     pub boot_version: String,
     /// version : 0.0.1-SNAPSHOT
     pub version: String,
+    /// template overlays copied into the project after extraction, see generator::overlay
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overlays: Vec<String>,
+    /// settings written to application.properties / .yaml after extraction
+    #[serde(default, skip_serializing_if = "ApplicationSettings::is_empty")]
+    pub application: ApplicationSettings,
+}
+
+/// Initial application settings, edited in the tui
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ApplicationSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_port: Option<u16>,
+    /// spring.application.name, Initializr sets it to the project name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datasource_url: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub active_profiles: Vec<String>,
+    /// any other property, dotted keys
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub properties: BTreeMap<String, String>,
+    /// properties for application-<profile> files, by profile
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl ApplicationSettings {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Properties of the main application file, the dedicated settings first
+    pub fn main_properties(&self) -> Vec<(String, String)> {
+        let mut properties = Vec::new();
+        if let Some(name) = &self.application_name {
+            properties.push(("spring.application.name".to_string(), name.clone()));
+        }
+        if !self.active_profiles.is_empty() {
+            properties.push(("spring.profiles.active".to_string(), self.active_profiles.join(",")));
+        }
+        if let Some(port) = self.server_port {
+            properties.push(("server.port".to_string(), port.to_string()));
+        }
+        if let Some(url) = &self.datasource_url {
+            properties.push(("spring.datasource.url".to_string(), url.clone()));
+        }
+        properties.extend(self.properties.iter().map(|(k, v)| (k.clone(), v.clone())));
+        properties
+    }
+}
+
+impl Default for SprintInitConfig {
+    fn default() -> Self {
+        Self {
+            config_version: CONFIG_VERSION,
+            project_type: "maven-project".to_string(),
+            language: "java".to_string(),
+            packaging: "jar".to_string(),
+            configuration_file_format: "properties".to_string(),
+            java_version: 17,
+            group_id: "com.example".to_string(),
+            artifact_id: "demo".to_string(),
+            name: "demo".to_string(),
+            description: "Demo project for Spring Boot".to_string(),
+            package_name: "com.example.demo".to_string(),
+            dependencies: "".to_string(),
+            // taken from the capabilities when the project is generated
+            boot_version: String::new(),
+            version: "0.0.1-SNAPSHOT".to_string(),
+            overlays: Vec::new(),
+            application: ApplicationSettings::default(),
+        }
+    }
 }
 
+impl SprintInitConfig {
+    /// Config preset with the defaults an Initializr instance advertises
+    pub fn from_capabilities(capabilities: &InitializrCapabilities) -> Self {
+        Self {
+            config_version: CONFIG_VERSION,
+            project_type: capabilities
+                .project_type
+                .as_ref()
+                .and_then(|pt| pt.default.clone())
+                .unwrap_or_else(|| "maven-project".to_string()),
+            language: capabilities
+                .language
+                .as_ref()
+                .and_then(|l| l.default.clone())
+                .unwrap_or_else(|| "java".to_string()),
+            packaging: capabilities
+                .packaging
+                .as_ref()
+                .and_then(|p| p.default.clone())
+                .unwrap_or_else(|| "jar".to_string()),
+            configuration_file_format: "properties".to_string(),
+            java_version: capabilities
+                .java_version
+                .as_ref()
+                .and_then(|jv| jv.default.as_ref())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(17),
+            group_id: capabilities
+                .group_id
+                .as_ref()
+                .and_then(|g| g.default.clone())
+                .unwrap_or_else(|| "com.example".to_string()),
+            artifact_id: capabilities
+                .artifact_id
+                .as_ref()
+                .and_then(|a| a.default.clone())
+                .unwrap_or_else(|| "demo".to_string()),
+            name: capabilities
+                .name
+                .as_ref()
+                .and_then(|n| n.default.clone())
+                .unwrap_or_else(|| "demo".to_string()),
+            description: capabilities
+                .description
+                .as_ref()
+                .and_then(|d| d.default.clone())
+                .unwrap_or_else(|| "Demo project for Spring Boot".to_string()),
+            package_name: capabilities
+                .package_name
+                .as_ref()
+                .and_then(|p| p.default.clone())
+                .unwrap_or_else(|| "com.example.demo".to_string()),
+            dependencies: "".to_string(),
+            boot_version: capabilities
+                .boot_version
+                .as_ref()
+                .and_then(|bv| bv.default.clone().or_else(|| bv.values.first().map(|v| v.id.clone())))
+                .unwrap_or_default(),
+            version: capabilities
+                .version
+                .as_ref()
+                .and_then(|v| v.default.clone())
+                .unwrap_or_else(|| "0.0.1-SNAPSHOT".to_string()),
+            overlays: Vec::new(),
+            application: ApplicationSettings::default(),
+        }
+    }
+}
+
+/// Trim a comma separated dependency list, dropping empty and repeated ids but keeping their order
+pub fn normalize_dependencies(ids: &str) -> String {
+    let mut unique: Vec<&str> = Vec::new();
+    for id in ids.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+        if !unique.contains(&id) {
+            unique.push(id);
+        }
+    }
+    unique.join(",")
+}
 
 #[derive(Serialize, Deserialize,Debug)]
 #[serde(rename_all = "camelCase")]
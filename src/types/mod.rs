@@ -1,3 +1,6 @@
 pub mod api;
+// library API, the binary doesn't build configs this way
+#[allow(dead_code)]
+pub mod builder;
 pub mod config;
 pub mod generic;
@@ -12,11 +12,13 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_args_derive_parser() {
         // Test that Args can be instantiated
         let args = Args {
             file: Some("config.yaml".to_string()),
             extract: false,
+            ..Default::default()
         };
         
         assert_eq!(args.file, Some("config.yaml".to_string()));
@@ -29,6 +31,7 @@ mod tests {
         let args = Args {
             file: None,
             extract: false,
+            ..Default::default()
         };
         
         // Test the logic inline since we can't easily mock Args::parse()
@@ -38,6 +41,7 @@ mod tests {
             Some(CliResult {
                 file: args.file.clone(),
                 extract: args.file.as_ref().map(|_| args.extract),
+                ..Default::default()
             })
         };
         
@@ -49,6 +53,7 @@ mod tests {
         let args = Args {
             file: Some("test.yaml".to_string()),
             extract: false,
+            ..Default::default()
         };
         
         let result = if args.file.is_none() {
@@ -57,6 +62,7 @@ mod tests {
             Some(CliResult {
                 file: args.file.clone(),
                 extract: args.file.as_ref().map(|_| args.extract),
+                ..Default::default()
             })
         };
         
@@ -71,6 +77,7 @@ mod tests {
         let args = Args {
             file: Some("config.json".to_string()),
             extract: true,
+            ..Default::default()
         };
         
         let result = if args.file.is_none() {
@@ -79,6 +86,7 @@ mod tests {
             Some(CliResult {
                 file: args.file.clone(),
                 extract: args.file.as_ref().map(|_| args.extract),
+                ..Default::default()
             })
         };
         
@@ -94,6 +102,7 @@ mod tests {
         let args_no_file = Args {
             file: None,
             extract: true,
+            ..Default::default()
         };
         
         let result = if args_no_file.file.is_none() {
@@ -102,6 +111,7 @@ mod tests {
             Some(CliResult {
                 file: args_no_file.file.clone(),
                 extract: args_no_file.file.as_ref().map(|_| args_no_file.extract),
+                ..Default::default()
             })
         };
         
@@ -111,6 +121,7 @@ mod tests {
         let args_with_file = Args {
             file: Some("config.toml".to_string()),
             extract: true,
+            ..Default::default()
         };
         
         let result = if args_with_file.file.is_none() {
@@ -119,6 +130,7 @@ mod tests {
             Some(CliResult {
                 file: args_with_file.file.clone(),
                 extract: args_with_file.file.as_ref().map(|_| args_with_file.extract),
+                ..Default::default()
             })
         };
         
@@ -141,6 +153,7 @@ mod tests {
             let args = Args {
                 file: Some(format.to_string()),
                 extract: false,
+                ..Default::default()
             };
             
             let result = if args.file.is_none() {
@@ -149,6 +162,7 @@ mod tests {
                 Some(CliResult {
                     file: args.file.clone(),
                     extract: args.file.as_ref().map(|_| args.extract),
+                    ..Default::default()
                 })
             };
             
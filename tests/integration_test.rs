@@ -1,12 +1,50 @@
 
 #[cfg(test)]
 mod integration_tests {
-    use spring_tui::config::create_project_from_config;
+    use spring_tui::api::{snapshot, transport};
+    use spring_tui::config::{create_project_from_config_in, load_config};
+    use spring_tui::generator::generate_project_into;
+    use spring_tui::generator::lock::{lock_path, regenerate, LockFile};
+    use spring_tui::types::config::GlobalConfig;
     use std::path::Path;
     use std::fs;
+    use std::io::Read;
+    use std::sync::{Arc, Once};
+
+    // Initializr responses are replayed from tests/fixtures/synthetic, hand-written archives and
+    // metadata shaped like start.spring.io's. SPRING_TUI_RECORD_FIXTURES=1 records real ones over them.
+    // The transport and snapshot dir are process globals and set once; each test gets its own output dir
+    fn replay_fixtures(name: &str) -> std::path::PathBuf {
+        static GLOBALS: Once = Once::new();
+        GLOBALS.call_once(|| {
+            transport::set_transport(Arc::new(transport::Fixtures::from_env(
+                Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/synthetic"),
+            )));
+            let dir = std::env::temp_dir().join(format!("spring-tui-snapshot-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            snapshot::set_snapshot_dir(&dir.to_string_lossy());
+        });
+        let scratch = std::env::temp_dir().join(format!("spring-tui-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&scratch);
+        scratch
+    }
+
+    fn entries(archive: &Path) -> Vec<String> {
+        let zip = zip::ZipArchive::new(fs::File::open(archive).unwrap()).unwrap();
+        zip.file_names().map(String::from).collect()
+    }
+
+    fn entry(archive: &Path, name: &str) -> String {
+        let mut zip = zip::ZipArchive::new(fs::File::open(archive).unwrap()).unwrap();
+        let mut content = String::new();
+        zip.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+        content
+    }
 
     #[tokio::test]
     async fn test_example_files_generation() {
+        let out = replay_fixtures("integration");
+
         // Map of config file to expected output file (zip)
         // Based on the content of the example files (artifactId)
         let examples = vec![
@@ -17,20 +55,83 @@ mod integration_tests {
 
         for (config_path, output_file) in examples {
             assert!(Path::new(config_path).exists(), "Example file {} not found in current directory", config_path);
-            
+
             println!("Testing generation from {}", config_path);
 
             // Run the generation (extract = false -> creates zip)
-            let result = create_project_from_config(config_path, false).await;
-            
+            let result = create_project_from_config_in(config_path, None, false, &out, &GlobalConfig::default(), None, None).await;
+
             assert!(result.is_ok(), "Failed to generate project from {}: {:?}", config_path, result.err());
 
             // Check if output file exists
-            let zip_path = Path::new(output_file);
+            let zip_path = out.join(output_file);
             assert!(zip_path.exists(), "Expected output file {} was not created", output_file);
 
-            // Cleanup
-            let _ = fs::remove_file(zip_path);
+            // The archive is the project the example describes
+            let config = load_config(config_path, None).unwrap();
+            if config.project_type.starts_with("maven") {
+                let pom = entry(&zip_path, "pom.xml");
+                assert!(pom.contains(&format!("<artifactId>{}</artifactId>", config.artifact_id)), "{}", pom);
+                assert!(pom.contains(&format!("<version>{}</version>", config.boot_version)), "{}", pom);
+            } else {
+                let settings = entry(&zip_path, "settings.gradle");
+                assert!(settings.contains(&format!("rootProject.name = '{}'", config.artifact_id)), "{}", settings);
+            }
+            let package = format!("src/main/{}/{}/", config.language, config.package_name.replace('.', "/"));
+            assert!(
+                entries(&zip_path).iter().any(|e| e.starts_with(&package) && e.contains("Application.")),
+                "No application class under {} in {}",
+                package,
+                output_file
+            );
         }
+
+        // Cleanup
+        let _ = fs::remove_dir_all(&out);
+    }
+
+    #[tokio::test]
+    async fn test_missing_boot_version_takes_the_initializr_default() {
+        let scratch = replay_fixtures("default-boot");
+        let mut config = load_config("example/rake-service-config.json", None).unwrap();
+        config.boot_version.clear();
+
+        generate_project_into(&config, false, &scratch, None, None).await.unwrap();
+
+        let lock = LockFile::read(&lock_path(&scratch, &config, false)).unwrap();
+        assert_eq!(lock.boot_version, "3.5.10");
+        let _ = fs::remove_dir_all(&scratch);
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_replays_the_lock() {
+        let scratch = replay_fixtures("regenerate");
+        let first = scratch.join("first");
+        let again = scratch.join("again");
+
+        create_project_from_config_in("example/security-config.toml", None, false, &first, &GlobalConfig::default(), None, None)
+            .await
+            .unwrap();
+        let lock = LockFile::read(&first.join("hopper.lock")).unwrap();
+        assert_eq!(lock.boot_version, "4.0.2");
+        assert_eq!(lock.dependencies.iter().map(|d| d.id.as_str()).collect::<Vec<_>>(), ["security"]);
+
+        let regenerated = regenerate(&lock, &again, false).await.unwrap();
+
+        assert!(regenerated.archive.exists());
+        assert!(regenerated.checksum_matches);
+        assert!(regenerated.drift.is_empty(), "{:?}", regenerated.drift);
+        assert!(regenerated.warnings.is_empty(), "{:?}", regenerated.warnings);
+        assert_eq!(LockFile::read(&again.join("hopper.lock")).unwrap(), lock);
+
+        // no coordinates for the Boot version: the project is still regenerated, without a drift check
+        let mut unknown = lock.clone();
+        unknown.config.boot_version = "9.9.9".to_string();
+        let regenerated = regenerate(&unknown, &scratch.join("unknown"), false).await.unwrap();
+        assert!(regenerated.archive.exists());
+        assert!(regenerated.drift.is_empty());
+        assert!(regenerated.warnings[0].contains("drift not checked"), "{:?}", regenerated.warnings);
+
+        let _ = fs::remove_dir_all(&scratch);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,31 @@
+// the crate used as a library: the entry points that generate into the working directory
+use spring_tui::api::{snapshot, transport};
+use spring_tui::config::{create_project_from_config, load_config};
+use spring_tui::generator::generate_project;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn generates_into_the_working_directory() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    transport::set_transport(Arc::new(transport::Fixtures::replay(manifest_dir.join("tests/fixtures/synthetic"))));
+    let scratch = std::env::temp_dir().join(format!("spring-tui-library-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&scratch);
+    fs::create_dir_all(&scratch).unwrap();
+    snapshot::set_snapshot_dir(&scratch.join("snapshot").to_string_lossy());
+    // the only test in this binary, nothing else depends on the working directory
+    std::env::set_current_dir(&scratch).unwrap();
+
+    let yaml = manifest_dir.join("example/rake-service-config.yaml");
+    create_project_from_config(&yaml.to_string_lossy(), false).await.unwrap();
+    assert!(scratch.join("rake.zip").exists());
+    // archive-only generations keep their lock next to the zip
+    assert!(scratch.join("rake.lock").exists());
+
+    let config = load_config(&manifest_dir.join("example/rake-service-config.json").to_string_lossy(), None).unwrap();
+    generate_project(&config, false).await.unwrap();
+    assert!(scratch.join("snake.zip").exists());
+
+    let _ = fs::remove_dir_all(&scratch);
+}